@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Compares the single-pass parser with the previous `split`/`collect` implementation.
+//!
+//! Run with `cargo bench --bench parse`.
+
+use app_version::{BuildMetadata, Prerelease, Version};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const INPUTS: &[&str] = &[
+    "1.2.3",
+    "0.14.0",
+    "10.20.30",
+    "2.0.0-rc.1",
+    "1.0.0-alpha.beta.1+build.1848",
+    "65535.65535.65535",
+];
+
+const ITERATIONS: u32 = 200_000;
+
+/// The parser as it was before the single-pass scanner.
+fn parse_split(s: &str) -> Option<Version> {
+    let (s, build) = match s.split_once('+') {
+        Some((_, "")) => return None,
+        Some((rest, build)) => (rest, BuildMetadata::new(build).ok()?),
+        None => (s, BuildMetadata::EMPTY),
+    };
+    let (s, pre) = match s.split_once('-') {
+        Some((_, "")) => return None,
+        Some((core, pre)) => (core, Prerelease::new(pre).ok()?),
+        None => (s, Prerelease::EMPTY),
+    };
+
+    let parts: Vec<&str> = s.split('.').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let major = parts[0].parse::<u16>().ok()?;
+    let minor = parts[1].parse::<u16>().ok()?;
+    let patch = parts[2].parse::<u16>().ok()?;
+
+    Some(
+        Version::new(major, minor, patch)
+            .with_prerelease(pre)
+            .with_build(build),
+    )
+}
+
+fn measure(name: &str, parse: impl Fn(&str) -> Option<Version>) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for input in INPUTS {
+            black_box(parse(black_box(input)));
+        }
+    }
+    let elapsed = start.elapsed();
+    let per_parse = elapsed / (ITERATIONS * INPUTS.len() as u32);
+    println!("{name:<12} {per_parse:>10.2?} per parse");
+    elapsed
+}
+
+fn main() {
+    for input in INPUTS {
+        assert_eq!(parse_split(input), input.parse().ok(), "{input}");
+    }
+
+    let split = measure("split", parse_split);
+    let from_str = measure("from_str", |s| s.parse().ok());
+    let bytes = measure("parse_bytes", |s| Version::parse_bytes(s.as_bytes()).ok());
+
+    println!(
+        "from_str is {:.2}x the speed of split",
+        split.as_secs_f64() / from_str.as_secs_f64()
+    );
+    println!(
+        "parse_bytes is {:.2}x the speed of split",
+        split.as_secs_f64() / bytes.as_secs_f64()
+    );
+}
@@ -0,0 +1,114 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, DeriveInput, Ident, LitStr, Token};
+
+/// Derives `app_version::VersionProvider`.
+///
+/// By default the version is the one of the enclosing package, from
+/// `CARGO_PKG_VERSION`. It can be overridden with a literal or with the name of
+/// an environment variable that is read at compile time:
+///
+/// ```
+/// use app_version::{Version, VersionProvider};
+///
+/// #[derive(VersionProvider)]
+/// struct Package;
+///
+/// #[derive(VersionProvider)]
+/// #[version("2.1.0")]
+/// struct Protocol;
+///
+/// #[derive(VersionProvider)]
+/// #[version(env = "CARGO_PKG_VERSION")]
+/// struct FromEnvironment;
+///
+/// assert_eq!(Protocol::version(), Version::new(2, 1, 0));
+/// assert_eq!(Package::version(), FromEnvironment::version());
+/// ```
+///
+/// The version is parsed at compile time, so an invalid version fails the build:
+///
+/// ```compile_fail
+/// use app_version::VersionProvider;
+///
+/// #[derive(VersionProvider)]
+/// #[version("2.1")]
+/// struct Protocol;
+/// ```
+#[proc_macro_derive(VersionProvider, attributes(version))]
+pub fn derive_version_provider(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The argument of a `#[version(...)]` attribute.
+enum VersionSource {
+    Literal(LitStr),
+    Env(LitStr),
+}
+
+impl Parse for VersionSource {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(VersionSource::Literal(input.parse()?));
+        }
+        let key: Ident = input.parse()?;
+        if key != "env" {
+            return Err(syn::Error::new(
+                key.span(),
+                "expected a version string or `env = \"VARIABLE\"`",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(VersionSource::Env(input.parse()?))
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut source = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("version"))
+    {
+        if source.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "duplicate `#[version(...)]` attribute",
+            ));
+        }
+        source = Some(attr.parse_args::<VersionSource>()?);
+    }
+
+    // Spanning the parse at the literal makes a compile error point at the offending version.
+    let version = match source {
+        None => quote! {
+            ::app_version::Version::parse_const(env!("CARGO_PKG_VERSION"))
+        },
+        Some(VersionSource::Literal(literal)) => quote_spanned! {literal.span()=>
+            ::app_version::Version::parse_const(#literal)
+        },
+        Some(VersionSource::Env(variable)) => quote_spanned! {variable.span()=>
+            ::app_version::Version::parse_const(env!(#variable))
+        },
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::app_version::VersionProvider for #name #ty_generics #where_clause {
+            fn version() -> ::app_version::Version {
+                const VERSION: ::app_version::Version = #version;
+                VERSION
+            }
+        }
+    })
+}
@@ -0,0 +1,44 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::{Version, VersionProvider};
+use std::marker::PhantomData;
+
+#[derive(VersionProvider)]
+struct Package;
+
+#[derive(VersionProvider)]
+#[version("2.1.0-beta.3")]
+struct Protocol;
+
+#[derive(VersionProvider)]
+#[version(env = "CARGO_PKG_VERSION")]
+struct FromEnvironment;
+
+#[derive(VersionProvider)]
+#[version("1.0.0")]
+#[allow(dead_code)]
+enum Message<T> {
+    Payload(PhantomData<T>),
+}
+
+#[test]
+fn defaults_to_package_version() {
+    assert_eq!(Package::version().to_string(), env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn literal_version() {
+    assert_eq!(Protocol::version().to_string(), "2.1.0-beta.3");
+}
+
+#[test]
+fn environment_variable() {
+    assert_eq!(FromEnvironment::version(), Package::version());
+}
+
+#[test]
+fn generic_type() {
+    assert_eq!(Message::<u8>::version(), Version::new(1, 0, 0));
+}
@@ -0,0 +1,481 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Command-line tool for release scripts, see `app-version --help`.
+//!
+//! Usage errors exit with 64 and invalid versions or requirements exit with 65, as in
+//! `sysexits.h`.
+use app_version::manifest::{self, VersionChange};
+use app_version::npm::Range;
+use app_version::workspace::{Plan, Workspace};
+use app_version::{
+    AtLeast, BumpLevel, CargoCaret, CompatibilityPolicy, Exact, LenientOptions, SameMajor,
+    SameMinor, Version, VersionReq,
+};
+use std::cmp::Ordering;
+use std::env;
+use std::io::{self, BufRead};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: app-version [--json] <command> [arguments]
+
+Commands:
+  bump <major|minor|patch> <version>      Print the next version
+  compare <a> <b>                         Exit with 0 if a == b, 1 if a < b, 2 if a > b
+  satisfies [--npm] <version> <req>       Exit with 0 if the version matches the requirement
+  sort [--reverse]                        Sort the versions on stdin, one per line
+  max                                     Print the highest version on stdin
+  validate [--strict|--lenient] <version> Exit with 0 if the version is valid
+  compatible [--policy <policy>] <a> <b>  Exit with 0 if the versions are compatible.
+                                          Policies: same-major (default), cargo-caret,
+                                          exact, same-minor, at-least=<version>
+  manifest get [<path>]                   Print the package version in a Cargo.toml
+  manifest bump <level> [<path>]          Increment the package version in place
+  manifest set <version> [<path>]         Set the package version in place
+  workspace bump <level> <package>...     Bump workspace members and the members whose
+    [--manifest-path <path>] [--dry-run]  requirements on them break, printing the plan
+
+Options:
+  --json  Print the result as JSON
+";
+
+const EXIT_USAGE: u8 = 64;
+const EXIT_DATA: u8 = 65;
+
+/// The result of a command, in both output formats.
+struct Outcome {
+    text: String,
+    json: String,
+    code: u8,
+}
+
+impl Outcome {
+    fn new(text: impl Into<String>, json: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            json: json.into(),
+            code: 0,
+        }
+    }
+
+    fn with_code(mut self, code: u8) -> Self {
+        self.code = code;
+        self
+    }
+}
+
+enum CliError {
+    Usage(String),
+    Data(String),
+}
+
+impl CliError {
+    const fn code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) => EXIT_USAGE,
+            CliError::Data(_) => EXIT_DATA,
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            CliError::Usage(message) | CliError::Data(message) => message,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let json = take_flag(&mut args, "--json");
+
+    if args.is_empty() || take_flag(&mut args, "--help") || take_flag(&mut args, "-h") {
+        print!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match run(&args[0], args[1..].to_vec(), json) {
+        Ok(outcome) => {
+            let output = if json { outcome.json } else { outcome.text };
+            if !output.is_empty() {
+                println!("{output}");
+            }
+            ExitCode::from(outcome.code)
+        }
+        Err(err) => {
+            if json {
+                println!("{{\"error\":{}}}", json_string(err.message()));
+            } else {
+                eprintln!("app-version: {}", err.message());
+                if let CliError::Usage(_) = err {
+                    eprint!("\n{USAGE}");
+                }
+            }
+            ExitCode::from(err.code())
+        }
+    }
+}
+
+fn run(command: &str, mut args: Vec<String>, json: bool) -> Result<Outcome, CliError> {
+    match command {
+        "bump" => {
+            let [level, version] = positional(args)?;
+            let next = parse_version(&version)?
+                .bumped(parse_level(&level)?)
+                .map_err(|err| CliError::Data(err.to_string()))?;
+            Ok(version_outcome(&next))
+        }
+        "compare" => {
+            let [a, b] = positional(args)?;
+            let ordering = parse_version(&a)?.cmp_precedence(&parse_version(&b)?);
+            let (name, code) = match ordering {
+                Ordering::Equal => ("equal", 0),
+                Ordering::Less => ("less", 1),
+                Ordering::Greater => ("greater", 2),
+            };
+            Ok(Outcome::new(name, format!("{{\"ordering\":\"{name}\"}}")).with_code(code))
+        }
+        "satisfies" => {
+            let npm = take_flag(&mut args, "--npm");
+            let [version, req] = positional(args)?;
+            let version = parse_version(&version)?;
+            let satisfies = if npm {
+                Range::parse(&req)
+                    .map_err(|err| CliError::Data(format!("'{req}': {err}")))?
+                    .matches(&version)
+            } else {
+                VersionReq::parse(&req)
+                    .map_err(|err| CliError::Data(format!("'{req}': {err}")))?
+                    .matches(&version)
+            };
+            Ok(bool_outcome("satisfies", satisfies))
+        }
+        "sort" => {
+            let reverse = take_flag(&mut args, "--reverse");
+            let [] = positional(args)?;
+            let mut versions = read_versions()?;
+            versions.sort();
+            if reverse {
+                versions.reverse();
+            }
+            let text: Vec<String> = versions.iter().map(Version::to_string).collect();
+            let json: Vec<String> = text.iter().map(|version| json_string(version)).collect();
+            Ok(Outcome::new(
+                text.join("\n"),
+                format!("[{}]", json.join(",")),
+            ))
+        }
+        "max" => {
+            let [] = positional(args)?;
+            let max = read_versions()?
+                .into_iter()
+                .max()
+                .ok_or_else(|| CliError::Data("no versions on stdin".to_string()))?;
+            Ok(version_outcome(&max))
+        }
+        "validate" => {
+            let lenient = take_flag(&mut args, "--lenient");
+            let strict = take_flag(&mut args, "--strict");
+            if lenient && strict {
+                return Err(CliError::Usage(
+                    "--strict and --lenient can not be combined".to_string(),
+                ));
+            }
+            let [version] = positional(args)?;
+            if lenient {
+                validate_lenient(&version)
+            } else {
+                Ok(match Version::parse_strict(&version) {
+                    Ok(parsed) => validation_outcome(&parsed, &[]),
+                    Err(err) => invalid_outcome(&err.to_string()),
+                })
+            }
+        }
+        "compatible" => {
+            let policy = take_option(&mut args, "--policy")?;
+            let [a, b] = positional(args)?;
+            let at_least;
+            let policy: &dyn CompatibilityPolicy = match policy.as_deref() {
+                None | Some("same-major") => &SameMajor,
+                Some("cargo-caret") => &CargoCaret,
+                Some("exact") => &Exact,
+                Some("same-minor") => &SameMinor,
+                Some(other) => {
+                    let Some(minimum) = other.strip_prefix("at-least=") else {
+                        return Err(CliError::Usage(format!("unknown policy '{other}'")));
+                    };
+                    at_least = AtLeast(minimum.parse().map_err(|err| {
+                        CliError::Usage(format!("invalid at-least version '{minimum}': {err}"))
+                    })?);
+                    &at_least
+                }
+            };
+            let compatible = parse_version(&a)?.is_compatible_with(&parse_version(&b)?, policy);
+            Ok(bool_outcome("compatible", compatible))
+        }
+        "manifest" => run_manifest(args),
+        "workspace" => run_workspace(args, json),
+        _ => Err(CliError::Usage(format!("unknown command '{command}'"))),
+    }
+}
+
+fn run_manifest(mut args: Vec<String>) -> Result<Outcome, CliError> {
+    if args.is_empty() {
+        return Err(CliError::Usage("manifest needs an action".to_string()));
+    }
+    let action = args.remove(0);
+    let manifest_error = |err: manifest::ManifestError| CliError::Data(err.to_string());
+    match action.as_str() {
+        "get" => {
+            let [path] = positional(with_default_path(args, 1))?;
+            let version = manifest::read_version(&path).map_err(manifest_error)?;
+            Ok(version_outcome(&version))
+        }
+        "bump" => {
+            let [level, path] = positional(with_default_path(args, 2))?;
+            let change = manifest::bump(&path, parse_level(&level)?).map_err(manifest_error)?;
+            Ok(change_outcome(&change))
+        }
+        "set" => {
+            let [version, path] = positional(with_default_path(args, 2))?;
+            let change = manifest::set(&path, &parse_version(&version)?).map_err(manifest_error)?;
+            Ok(change_outcome(&change))
+        }
+        _ => Err(CliError::Usage(format!(
+            "unknown manifest action '{action}'"
+        ))),
+    }
+}
+
+/// Prints the plan before anything is written, so it is shown even if applying fails.
+fn run_workspace(mut args: Vec<String>, json: bool) -> Result<Outcome, CliError> {
+    let dry_run = take_flag(&mut args, "--dry-run");
+    let path = take_option(&mut args, "--manifest-path")?.unwrap_or("Cargo.toml".to_string());
+    reject_options(&args)?;
+    if args.first().map(String::as_str) != Some("bump") {
+        return Err(CliError::Usage(
+            "workspace needs the action 'bump'".to_string(),
+        ));
+    }
+    if args.len() < 3 {
+        return Err(CliError::Usage(
+            "workspace bump needs a level and at least one package".to_string(),
+        ));
+    }
+    let level = parse_level(&args[1])?;
+    let bumps: Vec<(&str, BumpLevel)> = args[2..]
+        .iter()
+        .map(|name| (name.as_str(), level))
+        .collect();
+
+    let manifest_error = |err: manifest::ManifestError| CliError::Data(err.to_string());
+    let workspace = Workspace::load(&path).map_err(manifest_error)?;
+    let plan = workspace.plan(&bumps).map_err(manifest_error)?;
+    let outcome = plan_outcome(&plan, !dry_run);
+    if dry_run {
+        return Ok(outcome);
+    }
+    if !json {
+        println!("{}", outcome.text);
+    }
+    workspace.apply(&plan).map_err(manifest_error)?;
+    Ok(Outcome::new("applied", outcome.json))
+}
+
+/// Adds the default `Cargo.toml` path if only the path is missing from `args`.
+fn with_default_path(mut args: Vec<String>, count: usize) -> Vec<String> {
+    if args.len() + 1 == count {
+        args.push("Cargo.toml".to_string());
+    }
+    args
+}
+
+fn parse_level(level: &str) -> Result<BumpLevel, CliError> {
+    match level {
+        "major" => Ok(BumpLevel::Major),
+        "minor" => Ok(BumpLevel::Minor),
+        "patch" => Ok(BumpLevel::Patch),
+        _ => Err(CliError::Usage(format!("unknown bump level '{level}'"))),
+    }
+}
+
+fn validate_lenient(version: &str) -> Result<Outcome, CliError> {
+    Ok(
+        match Version::parse_lenient(version, &LenientOptions::default()) {
+            Ok(parsed) => {
+                let coercions: Vec<String> =
+                    parsed.coercions().iter().map(ToString::to_string).collect();
+                for coercion in &coercions {
+                    eprintln!("app-version: warning: {coercion}");
+                }
+                validation_outcome(&parsed.version(), &coercions)
+            }
+            Err(err) => invalid_outcome(&err.to_string()),
+        },
+    )
+}
+
+fn version_outcome(version: &Version) -> Outcome {
+    let version = version.to_string();
+    let json = format!("{{\"version\":{}}}", json_string(&version));
+    Outcome::new(version, json)
+}
+
+fn change_outcome(change: &VersionChange) -> Outcome {
+    let path = change.path.display().to_string();
+    let text = format!("{} -> {} in {}", change.previous, change.version, path);
+    let json = format!(
+        "{{\"path\":{},\"previous\":{},\"version\":{}}}",
+        json_string(&path),
+        json_string(&change.previous.to_string()),
+        json_string(&change.version.to_string())
+    );
+    Outcome::new(text, json)
+}
+
+fn plan_outcome(plan: &Plan, applied: bool) -> Outcome {
+    let bumps: Vec<String> = plan
+        .bumps()
+        .iter()
+        .map(|bump| {
+            format!(
+                "{{\"package\":{},\"previous\":{},\"version\":{},\"level\":{},\"reason\":{}}}",
+                json_string(&bump.package),
+                json_string(&bump.previous.to_string()),
+                json_string(&bump.version.to_string()),
+                json_string(&bump.level.to_string()),
+                json_string(&bump.reason.to_string())
+            )
+        })
+        .collect();
+    let requirements: Vec<String> = plan
+        .requirements()
+        .iter()
+        .map(|update| {
+            format!(
+                "{{\"path\":{},\"table\":{},\"dependency\":{},\"previous\":{},\"version\":{},\"requirement\":{}}}",
+                json_string(&update.path.display().to_string()),
+                json_string(&update.table),
+                json_string(&update.dependency),
+                json_string(&update.previous),
+                json_string(&update.version.to_string()),
+                update
+                    .requirement
+                    .as_deref()
+                    .map_or_else(|| "null".to_string(), json_string)
+            )
+        })
+        .collect();
+    let json = format!(
+        "{{\"bumps\":[{}],\"requirements\":[{}],\"applied\":{}}}",
+        bumps.join(","),
+        requirements.join(","),
+        applied
+    );
+    Outcome::new(plan.to_string().trim_end(), json)
+}
+
+fn bool_outcome(name: &str, value: bool) -> Outcome {
+    Outcome::new(value.to_string(), format!("{{\"{name}\":{value}}}")).with_code(u8::from(!value))
+}
+
+fn validation_outcome(version: &Version, coercions: &[String]) -> Outcome {
+    let version = version.to_string();
+    let coercions: Vec<String> = coercions
+        .iter()
+        .map(|coercion| json_string(coercion))
+        .collect();
+    let json = format!(
+        "{{\"valid\":true,\"version\":{},\"coercions\":[{}]}}",
+        json_string(&version),
+        coercions.join(",")
+    );
+    Outcome::new(version, json)
+}
+
+fn invalid_outcome(error: &str) -> Outcome {
+    let json = format!("{{\"valid\":false,\"error\":{}}}", json_string(error));
+    Outcome::new(format!("invalid: {error}"), json).with_code(1)
+}
+
+fn parse_version(s: &str) -> Result<Version, CliError> {
+    s.parse()
+        .map_err(|err| CliError::Data(format!("'{s}': {err}")))
+}
+
+fn read_versions() -> Result<Vec<Version>, CliError> {
+    let mut versions = Vec::new();
+    for line in io::stdin().lock().lines() {
+        let line = line.map_err(|err| CliError::Data(err.to_string()))?;
+        let line = line.trim();
+        if !line.is_empty() {
+            versions.push(parse_version(line)?);
+        }
+    }
+    Ok(versions)
+}
+
+/// Removes `flag` from `args`, returning `true` if it was present.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != len
+}
+
+/// Removes `name` and its value from `args`, accepting both `--name value` and `--name=value`.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, CliError> {
+    let prefix = format!("{name}=");
+    if let Some(index) = args.iter().position(|arg| arg.starts_with(&prefix)) {
+        return Ok(Some(args.remove(index)[prefix.len()..].to_string()));
+    }
+    let Some(index) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if index + 1 == args.len() {
+        return Err(CliError::Usage(format!("{name} needs a value")));
+    }
+    args.remove(index);
+    Ok(Some(args.remove(index)))
+}
+
+/// Checks that exactly `N` arguments are left, and that none of them is an unknown option.
+fn positional<const N: usize>(args: Vec<String>) -> Result<[String; N], CliError> {
+    reject_options(&args)?;
+    let found = args.len();
+    args.try_into()
+        .map_err(|_| CliError::Usage(format!("expected {N} arguments, found {found}")))
+}
+
+/// Fails on the first argument shaped like `-x` or `--name`, so values such as `-1` are
+/// left for the caller to reject as bad positional values.
+fn reject_options(args: &[String]) -> Result<(), CliError> {
+    let is_option = |arg: &&String| {
+        let name = arg.strip_prefix("--").or_else(|| arg.strip_prefix('-'));
+        name.and_then(|name| name.chars().next())
+            .is_some_and(|c| c.is_ascii_alphabetic())
+    };
+    match args.iter().find(is_option) {
+        Some(option) => Err(CliError::Usage(format!("unknown option '{option}'"))),
+        None => Ok(()),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if u32::from(c) < 0x20 => json.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
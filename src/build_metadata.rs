@@ -0,0 +1,138 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::identifiers::{Identifiers, IdentifiersError};
+use crate::VersionError;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// The build metadata of a version, e.g. `build.381.sha.9f3c1a` in `1.4.2+build.381.sha.9f3c1a`.
+///
+/// Build metadata is a list of dot-separated identifiers made of ASCII
+/// alphanumerics and hyphens. It identifies a specific build, but does not
+/// take part in precedence or compatibility checks. The identifiers are stored
+/// inline, so build metadata is limited to [`BuildMetadata::MAX_LEN`] bytes.
+///
+/// # Examples
+///
+/// ```
+/// use app_version::BuildMetadata;
+///
+/// let build: BuildMetadata = "build.381.sha.9f3c1a".parse().unwrap();
+/// assert_eq!(build.as_str(), "build.381.sha.9f3c1a");
+/// ```
+#[derive(Clone, Copy)]
+pub struct BuildMetadata {
+    identifiers: Identifiers<{ BuildMetadata::MAX_LEN }>,
+}
+
+impl BuildMetadata {
+    /// The maximum length, in bytes, of the build metadata.
+    pub const MAX_LEN: usize = 64;
+
+    /// The empty build metadata.
+    pub const EMPTY: Self = Self {
+        identifiers: Identifiers::EMPTY,
+    };
+
+    /// Creates build metadata from dot-separated identifiers.
+    ///
+    /// # Parameters
+    /// - `s`: The identifiers, e.g. `"sha.9f3c1a"`. An empty string gives [`BuildMetadata::EMPTY`].
+    ///
+    /// # Returns
+    /// The `BuildMetadata`, or a `VersionError` if `s` is not valid build metadata.
+    pub fn new(s: &str) -> Result<Self, VersionError> {
+        Self::from_bytes(s.as_bytes())
+    }
+
+    pub(crate) fn from_bytes(s: &[u8]) -> Result<Self, VersionError> {
+        Self::parse_const(s).map_err(|err| match err {
+            IdentifiersError::Invalid => VersionError::InvalidBuildMetadata,
+            IdentifiersError::TooLong => VersionError::BuildMetadataTooLong,
+        })
+    }
+
+    pub(crate) const fn parse_const(s: &[u8]) -> Result<Self, IdentifiersError> {
+        match Identifiers::parse(s, true) {
+            Ok(identifiers) => Ok(Self { identifiers }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns `true` if there are no identifiers.
+    pub const fn is_empty(&self) -> bool {
+        self.identifiers.is_empty()
+    }
+
+    /// Returns the identifiers as a dot-separated string.
+    pub fn as_str(&self) -> &str {
+        self.identifiers.as_str()
+    }
+
+    /// Returns an iterator over the individual identifiers.
+    pub fn identifiers(&self) -> impl Iterator<Item = &str> {
+        self.as_str()
+            .split('.')
+            .filter(|identifier| !identifier.is_empty())
+    }
+}
+
+impl Default for BuildMetadata {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl Ord for BuildMetadata {
+    /// Orders build metadata lexicographically.
+    ///
+    /// This ordering carries no meaning under SemVer, it only exists so that
+    /// versions differing in build metadata have a deterministic order.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialOrd for BuildMetadata {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for BuildMetadata {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for BuildMetadata {}
+
+impl Hash for BuildMetadata {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for BuildMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BuildMetadata(\"{}\")", self.as_str())
+    }
+}
+
+impl fmt::Display for BuildMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BuildMetadata {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
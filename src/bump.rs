@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::{BuildMetadata, Component, Prerelease, Version, VersionError};
+use std::fmt;
+
+/// Which component of a version to increment.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum BumpLevel {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for BumpLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BumpLevel::Patch => write!(f, "patch"),
+            BumpLevel::Minor => write!(f, "minor"),
+            BumpLevel::Major => write!(f, "major"),
+        }
+    }
+}
+
+impl Version {
+    /// Returns the version with the given component incremented.
+    ///
+    /// Lower components are reset to 0, and the pre-release and build metadata are cleared.
+    ///
+    /// # Parameters
+    /// - `level`: The component to increment.
+    ///
+    /// # Returns
+    /// The next `Version`, or `VersionError::Overflow` if the component is already `u16::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use app_version::{BumpLevel, Version};
+    ///
+    /// let version = Version::new(1, 2, 3);
+    /// assert_eq!(version.bumped(BumpLevel::Minor).unwrap(), Version::new(1, 3, 0));
+    /// assert!(Version::new(1, 2, u16::MAX).bumped(BumpLevel::Patch).is_err());
+    /// ```
+    pub fn bumped(&self, level: BumpLevel) -> Result<Self, VersionError> {
+        Ok(match level {
+            BumpLevel::Major => Self::new(
+                self.major
+                    .checked_add(1)
+                    .ok_or(VersionError::Overflow(Component::Major))?,
+                0,
+                0,
+            ),
+            BumpLevel::Minor => Self::new(
+                self.major,
+                self.minor
+                    .checked_add(1)
+                    .ok_or(VersionError::Overflow(Component::Minor))?,
+                0,
+            ),
+            BumpLevel::Patch => Self::new(
+                self.major,
+                self.minor,
+                self.patch
+                    .checked_add(1)
+                    .ok_or(VersionError::Overflow(Component::Patch))?,
+            ),
+        })
+    }
+
+    /// Returns the version with the given component incremented, or with the
+    /// component kept if it is already `u16::MAX`.
+    ///
+    /// Like [`Version::bumped`], the pre-release and build metadata are always cleared,
+    /// so `1.2.65535-rc.1` saturates to `1.2.65535`.
+    ///
+    /// # Parameters
+    /// - `level`: The component to increment.
+    ///
+    /// # Returns
+    /// A `Version` instance.
+    pub fn saturating_bumped(&self, level: BumpLevel) -> Self {
+        self.bumped(level).unwrap_or_else(|_| {
+            self.with_prerelease(Prerelease::EMPTY)
+                .with_build(BuildMetadata::EMPTY)
+        })
+    }
+
+    /// Returns the version with the patch version incremented.
+    ///
+    /// # Returns
+    /// The next `Version`, or `VersionError::Overflow` if patch is already `u16::MAX`.
+    pub fn checked_increment_patch(&self) -> Result<Self, VersionError> {
+        self.bumped(BumpLevel::Patch)
+    }
+
+    /// Returns the version with the minor version incremented and patch reset to 0.
+    ///
+    /// # Returns
+    /// The next `Version`, or `VersionError::Overflow` if minor is already `u16::MAX`.
+    pub fn checked_increment_minor(&self) -> Result<Self, VersionError> {
+        self.bumped(BumpLevel::Minor)
+    }
+
+    /// Returns the version with the major version incremented and minor and patch reset to 0.
+    ///
+    /// # Returns
+    /// The next `Version`, or `VersionError::Overflow` if major is already `u16::MAX`.
+    pub fn checked_increment_major(&self) -> Result<Self, VersionError> {
+        self.bumped(BumpLevel::Major)
+    }
+
+    /// Returns the version with the patch version incremented, see
+    /// [`Version::saturating_bumped`].
+    pub fn saturating_increment_patch(&self) -> Self {
+        self.saturating_bumped(BumpLevel::Patch)
+    }
+
+    /// Returns the version with the minor version incremented and patch reset to 0,
+    /// see [`Version::saturating_bumped`].
+    pub fn saturating_increment_minor(&self) -> Self {
+        self.saturating_bumped(BumpLevel::Minor)
+    }
+
+    /// Returns the version with the major version incremented and minor and patch
+    /// reset to 0, see [`Version::saturating_bumped`].
+    pub fn saturating_increment_major(&self) -> Self {
+        self.saturating_bumped(BumpLevel::Major)
+    }
+}
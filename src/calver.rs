@@ -0,0 +1,428 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Calendar versions, see <https://calver.org>.
+//!
+//! A [`Format`] such as `YYYY.0M.MICRO` describes the dot-separated segments of a
+//! [`CalVer`]:
+//!
+//! | Segment | Meaning                            | Examples        |
+//! |---------|------------------------------------|-----------------|
+//! | `YYYY`  | Full year                          | `2006`, `2026`  |
+//! | `YY`    | Year since 2000                    | `6`, `26`       |
+//! | `0Y`    | Zero-padded year since 2000        | `06`, `26`      |
+//! | `MM`    | Month                              | `1`, `10`       |
+//! | `0M`    | Zero-padded month                  | `01`, `10`      |
+//! | `DD`    | Day of the month                   | `3`, `31`       |
+//! | `0D`    | Zero-padded day of the month       | `03`, `31`      |
+//! | `MICRO` | Release number within the period   | `0`, `3`        |
+//!
+//! A format starts with a year, which may be followed by a month and then a day.
+//! `MICRO` can only be the last segment.
+//!
+//! # Examples
+//!
+//! ```
+//! use app_version::calver::{CalVer, Format};
+//! use app_version::Version;
+//!
+//! let format: Format = "YYYY.0M.MICRO".parse().unwrap();
+//! let version = CalVer::parse("2026.10.3", format).unwrap();
+//! assert_eq!(version.month(), Some(10));
+//! assert_eq!(version.to_version().unwrap(), Version::new(2026, 10, 3));
+//!
+//! assert!(CalVer::parse("2026.13.0", format).is_err());
+//! ```
+use crate::{Version, VersionError};
+use std::fmt;
+use std::str::FromStr;
+
+/// A segment of a calendar version [`Format`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Segment {
+    /// `YYYY`
+    FullYear,
+    /// `YY`
+    ShortYear,
+    /// `0Y`
+    PaddedYear,
+    /// `MM`
+    Month,
+    /// `0M`
+    PaddedMonth,
+    /// `DD`
+    Day,
+    /// `0D`
+    PaddedDay,
+    /// `MICRO`
+    Micro,
+}
+
+/// The part of the date a segment holds, in order of significance.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Field {
+    Year,
+    Month,
+    Day,
+    Micro,
+}
+
+impl Segment {
+    const ALL: [Segment; 8] = [
+        Segment::FullYear,
+        Segment::ShortYear,
+        Segment::PaddedYear,
+        Segment::Month,
+        Segment::PaddedMonth,
+        Segment::Day,
+        Segment::PaddedDay,
+        Segment::Micro,
+    ];
+
+    /// Returns the token used in a format string, e.g. `"0M"`.
+    pub const fn token(&self) -> &'static str {
+        match self {
+            Segment::FullYear => "YYYY",
+            Segment::ShortYear => "YY",
+            Segment::PaddedYear => "0Y",
+            Segment::Month => "MM",
+            Segment::PaddedMonth => "0M",
+            Segment::Day => "DD",
+            Segment::PaddedDay => "0D",
+            Segment::Micro => "MICRO",
+        }
+    }
+
+    const fn field(&self) -> Field {
+        match self {
+            Segment::FullYear | Segment::ShortYear | Segment::PaddedYear => Field::Year,
+            Segment::Month | Segment::PaddedMonth => Field::Month,
+            Segment::Day | Segment::PaddedDay => Field::Day,
+            Segment::Micro => Field::Micro,
+        }
+    }
+
+    const fn is_padded(&self) -> bool {
+        matches!(
+            self,
+            Segment::PaddedYear | Segment::PaddedMonth | Segment::PaddedDay
+        )
+    }
+
+    /// Parses the text of this segment in a calendar version.
+    fn parse_value(&self, text: &str) -> Result<u16, VersionError> {
+        let well_formed = if self.is_padded() {
+            text.len() == 2 || (text.len() > 2 && !text.starts_with('0'))
+        } else {
+            text == "0" || !text.starts_with('0')
+        };
+        if !well_formed || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(VersionError::InvalidCalVer);
+        }
+        text.parse().map_err(|_| VersionError::InvalidCalVer)
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.token())
+    }
+}
+
+const MAX_SEGMENTS: usize = 4;
+
+/// The layout of a calendar version, e.g. `YYYY.0M.MICRO`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Format {
+    segments: [Segment; MAX_SEGMENTS],
+    len: u8,
+}
+
+impl Format {
+    /// Parses a format string, e.g. `"YY.0M"`.
+    ///
+    /// # Parameters
+    /// - `s`: Dot-separated segment tokens.
+    ///
+    /// # Returns
+    /// The `Format`, or `VersionError::InvalidCalVerFormat` if a token is unknown or the
+    /// segments are not in year, month, day, micro order.
+    pub fn parse(s: &str) -> Result<Self, VersionError> {
+        let mut segments = [Segment::Micro; MAX_SEGMENTS];
+        let mut len = 0;
+        for token in s.split('.') {
+            let segment = Segment::ALL
+                .into_iter()
+                .find(|segment| segment.token() == token)
+                .ok_or(VersionError::InvalidCalVerFormat)?;
+            let field = segment.field();
+            let allowed = match segments[..len].last().map(Segment::field) {
+                None => field == Field::Year,
+                Some(Field::Year) => matches!(field, Field::Month | Field::Micro),
+                Some(Field::Month) => matches!(field, Field::Day | Field::Micro),
+                Some(Field::Day) => field == Field::Micro,
+                Some(Field::Micro) => false,
+            };
+            if !allowed {
+                return Err(VersionError::InvalidCalVerFormat);
+            }
+            segments[len] = segment;
+            len += 1;
+        }
+        Ok(Self {
+            segments,
+            len: len as u8,
+        })
+    }
+
+    /// Returns the segments, most significant first.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments[..self.len as usize]
+    }
+
+    fn has(&self, field: Field) -> bool {
+        self.segments()
+            .iter()
+            .any(|segment| segment.field() == field)
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.segments().iter().enumerate() {
+            if index > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Format {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// A calendar version, e.g. `2026.10.3` in the `YYYY.0M.MICRO` format.
+///
+/// Calendar versions are ordered by date, then by micro. Versions in different
+/// formats with the same values are ordered by their format.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CalVer {
+    year: u16,
+    month: u8,
+    day: u8,
+    micro: u16,
+    format: Format,
+}
+
+impl CalVer {
+    /// Creates a calendar version, validating the date.
+    ///
+    /// Parts that are not in the format are ignored.
+    ///
+    /// # Parameters
+    /// - `format`: The layout used when displaying the version.
+    /// - `year`: The full year, e.g. `2026`. Must be at least 2000 for `YY` and `0Y`.
+    /// - `month`: The month, from 1 to 12.
+    /// - `day`: The day of the month, from 1.
+    /// - `micro`: The release number within the period.
+    ///
+    /// # Returns
+    /// The `CalVer`, or `VersionError::InvalidDate` if the date is not valid.
+    pub fn new(
+        format: Format,
+        year: u16,
+        month: u8,
+        day: u8,
+        micro: u16,
+    ) -> Result<Self, VersionError> {
+        let short_year = format
+            .segments()
+            .iter()
+            .any(|segment| matches!(segment, Segment::ShortYear | Segment::PaddedYear));
+        let month = if format.has(Field::Month) { month } else { 0 };
+        let day = if format.has(Field::Day) { day } else { 0 };
+        let micro = if format.has(Field::Micro) { micro } else { 0 };
+
+        if year == 0
+            || (short_year && year < 2000)
+            || (format.has(Field::Month) && !(1..=12).contains(&month))
+            || (format.has(Field::Day) && !(1..=days_in_month(year, month)).contains(&day))
+        {
+            return Err(VersionError::InvalidDate);
+        }
+
+        Ok(Self {
+            year,
+            month,
+            day,
+            micro,
+            format,
+        })
+    }
+
+    /// Parses a calendar version in the given format.
+    ///
+    /// # Parameters
+    /// - `s`: The version string, e.g. `"2026.10.3"`.
+    /// - `format`: The expected layout.
+    ///
+    /// # Returns
+    /// The `CalVer`, or a `VersionError` if `s` does not match the format or is not a
+    /// valid date.
+    pub fn parse(s: &str, format: Format) -> Result<Self, VersionError> {
+        let mut values = [0; MAX_SEGMENTS];
+        let mut found = 0;
+        for text in s.split('.') {
+            if let Some(segment) = format.segments().get(found) {
+                values[found] = segment.parse_value(text)?;
+            }
+            found += 1;
+        }
+        if found != format.segments().len() {
+            return Err(VersionError::InvalidComponentCount {
+                expected: format.segments().len(),
+                found,
+            });
+        }
+        Self::from_values(format, &values[..found])
+    }
+
+    /// Converts a `Version` laid out as `format`, e.g. `2026.10.3` for `YYYY.0M.MICRO`.
+    ///
+    /// Version components after the last segment must be 0.
+    ///
+    /// # Parameters
+    /// - `version`: The version to convert. Must not have a pre-release or build metadata.
+    /// - `format`: A layout with at most three segments.
+    ///
+    /// # Returns
+    /// The `CalVer`, or a `VersionError` if the version does not fit the format.
+    pub fn from_version(version: &Version, format: Format) -> Result<Self, VersionError> {
+        let len = format.segments().len();
+        if len > 3 {
+            return Err(VersionError::InvalidComponentCount {
+                expected: 3,
+                found: len,
+            });
+        }
+        let values = [version.major(), version.minor(), version.patch()];
+        if version.is_prerelease()
+            || !version.build().is_empty()
+            || values[len..].iter().any(|&value| value != 0)
+        {
+            return Err(VersionError::InvalidCalVer);
+        }
+        Self::from_values(format, &values[..len])
+    }
+
+    /// Converts to a `Version` with the segments as major, minor and patch.
+    ///
+    /// Missing components are 0. For a given format, the versions are ordered the
+    /// same way as the calendar versions.
+    ///
+    /// # Returns
+    /// The `Version`, or `VersionError::InvalidComponentCount` if the format has more
+    /// than three segments.
+    pub fn to_version(&self) -> Result<Version, VersionError> {
+        let segments = self.format.segments();
+        if segments.len() > 3 {
+            return Err(VersionError::InvalidComponentCount {
+                expected: 3,
+                found: segments.len(),
+            });
+        }
+        let mut values = [0; 3];
+        for (value, segment) in values.iter_mut().zip(segments) {
+            *value = self.value(*segment);
+        }
+        Ok(Version::new(values[0], values[1], values[2]))
+    }
+
+    /// Returns the full year, e.g. `2026`.
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month, from 1 to 12, if the format has one.
+    pub fn month(&self) -> Option<u8> {
+        self.format.has(Field::Month).then_some(self.month)
+    }
+
+    /// Returns the day of the month, if the format has one.
+    pub fn day(&self) -> Option<u8> {
+        self.format.has(Field::Day).then_some(self.day)
+    }
+
+    /// Returns the release number within the period, if the format has one.
+    pub fn micro(&self) -> Option<u16> {
+        self.format.has(Field::Micro).then_some(self.micro)
+    }
+
+    /// Returns the format used when displaying the version.
+    pub const fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Creates a calendar version from the numeric value of each segment in `format`.
+    fn from_values(format: Format, values: &[u16]) -> Result<Self, VersionError> {
+        let (mut year, mut month, mut day, mut micro) = (0, 0, 0, 0);
+        for (segment, &value) in format.segments().iter().zip(values) {
+            let narrow = |value: u16| u8::try_from(value).map_err(|_| VersionError::InvalidDate);
+            match segment {
+                Segment::FullYear => year = value,
+                Segment::ShortYear | Segment::PaddedYear => {
+                    year = value.checked_add(2000).ok_or(VersionError::InvalidDate)?;
+                }
+                Segment::Month | Segment::PaddedMonth => month = narrow(value)?,
+                Segment::Day | Segment::PaddedDay => day = narrow(value)?,
+                Segment::Micro => micro = value,
+            }
+        }
+        Self::new(format, year, month, day, micro)
+    }
+
+    /// Returns the numeric value shown for `segment`.
+    fn value(&self, segment: Segment) -> u16 {
+        match segment.field() {
+            Field::Year if segment == Segment::FullYear => self.year,
+            Field::Year => self.year - 2000,
+            Field::Month => self.month.into(),
+            Field::Day => self.day.into(),
+            Field::Micro => self.micro,
+        }
+    }
+}
+
+impl fmt::Display for CalVer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.format.segments().iter().enumerate() {
+            if index > 0 {
+                write!(f, ".")?;
+            }
+            if segment.is_padded() {
+                write!(f, "{:02}", self.value(*segment))?;
+            } else {
+                write!(f, "{}", self.value(*segment))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
@@ -0,0 +1,151 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::Version;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A rule deciding whether two versions can work together.
+///
+/// Built-in policies are [`SameMajor`], [`CargoCaret`], [`Exact`], [`SameMinor`]
+/// and [`AtLeast`]. Any `Fn(&Version, &Version) -> bool` is a policy as well.
+///
+/// # Examples
+///
+/// ```
+/// use app_version::{CargoCaret, SameMajor, Version};
+///
+/// let a = Version::new(0, 1, 0);
+/// let b = Version::new(0, 2, 0);
+/// assert!(a.is_compatible_with(&b, &SameMajor));
+/// assert!(!a.is_compatible_with(&b, &CargoCaret));
+///
+/// let same_patch = |a: &Version, b: &Version| a.patch() == b.patch();
+/// assert!(a.is_compatible_with(&b, &same_patch));
+/// ```
+pub trait CompatibilityPolicy {
+    /// Checks if the `local` and `remote` versions are compatible.
+    fn is_compatible(&self, local: &Version, remote: &Version) -> bool;
+}
+
+impl<F: Fn(&Version, &Version) -> bool> CompatibilityPolicy for F {
+    fn is_compatible(&self, local: &Version, remote: &Version) -> bool {
+        self(local, remote)
+    }
+}
+
+/// Compatible when the major versions are equal.
+///
+/// This is the policy used by [`Version::is_compatible`].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct SameMajor;
+
+impl CompatibilityPolicy for SameMajor {
+    fn is_compatible(&self, local: &Version, remote: &Version) -> bool {
+        local.major() == remote.major()
+    }
+}
+
+/// Compatible when the leftmost non-zero component is equal, as for Cargo's `^` requirements.
+///
+/// `1.2.0` and `1.9.0` are compatible, `0.1.0` and `0.2.0` are not, and
+/// `0.0.x` versions are only compatible with the same patch version.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct CargoCaret;
+
+impl CompatibilityPolicy for CargoCaret {
+    fn is_compatible(&self, local: &Version, remote: &Version) -> bool {
+        if local.major() != remote.major() {
+            return false;
+        }
+        if local.major() > 0 {
+            return true;
+        }
+        if local.minor() != remote.minor() {
+            return false;
+        }
+        local.minor() > 0 || local.patch() == remote.patch()
+    }
+}
+
+/// Compatible only when the versions have equal precedence, e.g. for lockstep simulations.
+///
+/// Build metadata is ignored.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Exact;
+
+impl CompatibilityPolicy for Exact {
+    fn is_compatible(&self, local: &Version, remote: &Version) -> bool {
+        local.cmp_precedence(remote) == Ordering::Equal
+    }
+}
+
+/// Compatible when both the major and minor versions are equal.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct SameMinor;
+
+impl CompatibilityPolicy for SameMinor {
+    fn is_compatible(&self, local: &Version, remote: &Version) -> bool {
+        local.major() == remote.major() && local.minor() == remote.minor()
+    }
+}
+
+/// Compatible when both versions are at least the given minimum version.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct AtLeast(pub Version);
+
+impl CompatibilityPolicy for AtLeast {
+    fn is_compatible(&self, local: &Version, remote: &Version) -> bool {
+        local.cmp_precedence(&self.0) != Ordering::Less
+            && remote.cmp_precedence(&self.0) != Ordering::Less
+    }
+}
+
+/// The answer to whether data written by one version can be read by another.
+///
+/// Returned by [`Version::can_read_data_from`] and [`Version::can_be_read_by`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ReadCompatibility {
+    /// The reader can read the data.
+    Compatible,
+    /// The reader does not meet the policy, or is older than a writer it can not read.
+    ReaderTooOld,
+    /// The writer does not meet the policy, or is older than the reader supports.
+    WriterTooOld,
+}
+
+impl ReadCompatibility {
+    /// Returns `true` if the reader can read the data.
+    pub const fn is_compatible(&self) -> bool {
+        matches!(self, ReadCompatibility::Compatible)
+    }
+
+    pub(crate) fn check<P: CompatibilityPolicy + ?Sized>(
+        reader: &Version,
+        writer: &Version,
+        policy: &P,
+    ) -> Self {
+        if policy.is_compatible(reader, writer) {
+            ReadCompatibility::Compatible
+        } else if !policy.is_compatible(reader, reader) {
+            ReadCompatibility::ReaderTooOld
+        } else if !policy.is_compatible(writer, writer) {
+            ReadCompatibility::WriterTooOld
+        } else if writer.cmp_precedence(reader) == Ordering::Greater {
+            ReadCompatibility::ReaderTooOld
+        } else {
+            ReadCompatibility::WriterTooOld
+        }
+    }
+}
+
+impl fmt::Display for ReadCompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadCompatibility::Compatible => write!(f, "Compatible"),
+            ReadCompatibility::ReaderTooOld => write!(f, "Reader is too old"),
+            ReadCompatibility::WriterTooOld => write!(f, "Writer is too old"),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use std::fmt;
+
+/// A component of a version.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Component {
+    Major,
+    Minor,
+    Patch,
+    Prerelease,
+}
+
+impl Component {
+    pub(crate) const fn code(self) -> u8 {
+        match self {
+            Component::Major => 1,
+            Component::Minor => 2,
+            Component::Patch => 3,
+            Component::Prerelease => 4,
+        }
+    }
+
+    /// Returns `None` for an unknown code, and `Some(None)` for the code of no component.
+    pub(crate) const fn from_code(code: u8) -> Option<Option<Self>> {
+        match code {
+            0 => Some(None),
+            1 => Some(Some(Component::Major)),
+            2 => Some(Some(Component::Minor)),
+            3 => Some(Some(Component::Patch)),
+            4 => Some(Some(Component::Prerelease)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Component::Major => write!(f, "major"),
+            Component::Minor => write!(f, "minor"),
+            Component::Patch => write!(f, "patch"),
+            Component::Prerelease => write!(f, "pre-release"),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::identifiers::IdentifiersError;
+use crate::{BuildMetadata, Prerelease, Version};
+
+impl Version {
+    /// Parses a version string in `const` context.
+    ///
+    /// Accepts the same `major.minor.patch[-pre-release][+build]` syntax as `FromStr`.
+    /// Use [`package_version!`](crate::package_version) to get the version of the
+    /// enclosing package.
+    ///
+    /// # Parameters
+    /// - `s`: The version string, e.g. `"1.2.3"`.
+    ///
+    /// # Returns
+    /// A `Version` instance.
+    ///
+    /// # Panics
+    /// If `s` is not a valid version. When evaluated in `const` context, this is
+    /// reported as a compile error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use app_version::Version;
+    ///
+    /// const PROTOCOL_VERSION: Version = Version::parse_const("1.4.2-rc.1");
+    /// assert_eq!(PROTOCOL_VERSION.minor(), 4);
+    /// assert!(PROTOCOL_VERSION.is_prerelease());
+    /// ```
+    ///
+    /// An invalid version fails to compile:
+    ///
+    /// ```compile_fail
+    /// use app_version::Version;
+    ///
+    /// const PROTOCOL_VERSION: Version = Version::parse_const("1.4");
+    /// ```
+    pub const fn parse_const(s: &str) -> Self {
+        match parse(s.as_bytes()) {
+            Ok(version) => version,
+            Err(message) => panic!("{}", message),
+        }
+    }
+}
+
+const fn split_once(s: &[u8], separator: u8) -> Option<(&[u8], &[u8])> {
+    let mut i = 0;
+    while i < s.len() {
+        if s[i] == separator {
+            let (before, after) = s.split_at(i);
+            return Some((before, after.split_at(1).1));
+        }
+        i += 1;
+    }
+    None
+}
+
+const fn parse(s: &[u8]) -> Result<Version, &'static str> {
+    let (s, build) = match split_once(s, b'+') {
+        Some((_, [])) => return Err("invalid version: build metadata is empty"),
+        Some((rest, build)) => match BuildMetadata::parse_const(build) {
+            Ok(build) => (rest, build),
+            Err(IdentifiersError::Invalid) => {
+                return Err("invalid version: invalid build metadata")
+            }
+            Err(IdentifiersError::TooLong) => {
+                return Err("invalid version: build metadata is too long")
+            }
+        },
+        None => (s, BuildMetadata::EMPTY),
+    };
+    let (s, pre) = match split_once(s, b'-') {
+        Some((_, [])) => return Err("invalid version: pre-release is empty"),
+        Some((rest, pre)) => match Prerelease::parse_const(pre) {
+            Ok(pre) => (rest, pre),
+            Err(IdentifiersError::Invalid) => return Err("invalid version: invalid pre-release"),
+            Err(IdentifiersError::TooLong) => {
+                return Err("invalid version: pre-release is too long")
+            }
+        },
+        None => (s, Prerelease::EMPTY),
+    };
+
+    let mut numbers = [0u16; 3];
+    let mut index = 0;
+    let mut value: u32 = 0;
+    let mut digits = 0;
+    let mut i = 0;
+    while i <= s.len() {
+        if i == s.len() || s[i] == b'.' {
+            if digits == 0 {
+                return Err("invalid version: expected major.minor.patch");
+            }
+            if index == numbers.len() {
+                return Err("invalid version: expected major.minor.patch");
+            }
+            numbers[index] = value as u16;
+            index += 1;
+            value = 0;
+            digits = 0;
+        } else if s[i].is_ascii_digit() {
+            value = value * 10 + (s[i] - b'0') as u32;
+            if value > u16::MAX as u32 {
+                return Err("invalid version: component does not fit in u16");
+            }
+            digits += 1;
+        } else {
+            return Err("invalid version: component is not a number");
+        }
+        i += 1;
+    }
+    if index != numbers.len() {
+        return Err("invalid version: expected major.minor.patch");
+    }
+
+    Ok(Version::new(numbers[0], numbers[1], numbers[2])
+        .with_prerelease(pre)
+        .with_build(build))
+}
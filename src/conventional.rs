@@ -0,0 +1,174 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Bump recommendations from [Conventional Commits](https://www.conventionalcommits.org).
+//!
+//! Commit messages are passed in as plain strings, so any source of commits works.
+//!
+//! | Commit                                   | Bump  |
+//! |------------------------------------------|-------|
+//! | `fix: ...`                               | patch |
+//! | `feat: ...`                              | minor |
+//! | `feat!: ...`, `BREAKING CHANGE:` footer  | major |
+//!
+//! Other types, such as `docs` or `chore`, and messages that are not Conventional
+//! Commits do not need a release. Before 1.0.0, a breaking change bumps the minor
+//! version instead of the major version.
+//!
+//! # Examples
+//!
+//! ```
+//! use app_version::conventional::recommend;
+//! use app_version::{BumpLevel, Version};
+//!
+//! let commits = [
+//!     "fix(parser): accept trailing newline",
+//!     "feat: add CalVer support",
+//!     "docs: update README",
+//! ];
+//! let recommendation = recommend(&Version::new(1, 4, 2), commits).unwrap().unwrap();
+//! assert_eq!(recommendation.level(), BumpLevel::Minor);
+//! assert_eq!(recommendation.version(), Version::new(1, 5, 0));
+//! ```
+use crate::{BumpLevel, Version, VersionError};
+
+/// The parsed header and breaking change footer of a commit message.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Commit<'a> {
+    kind: &'a str,
+    scope: Option<&'a str>,
+    breaking: bool,
+    description: &'a str,
+}
+
+impl<'a> Commit<'a> {
+    /// Parses a commit message, e.g. `"feat(parser)!: drop support for v1"`.
+    ///
+    /// # Parameters
+    /// - `message`: The full commit message. Lines after the header are searched for a
+    ///   `BREAKING CHANGE:` or `BREAKING-CHANGE:` footer.
+    ///
+    /// # Returns
+    /// The `Commit`, or `None` if the header is not `type(scope)!: description`.
+    pub fn parse(message: &'a str) -> Option<Self> {
+        let mut lines = message.lines();
+        let (prefix, description) = lines.next()?.split_once(": ")?;
+        let description = description.trim();
+
+        let (prefix, bang) = match prefix.strip_suffix('!') {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
+        let (kind, scope) = match prefix.split_once('(') {
+            Some((kind, scope)) => (kind, Some(scope.strip_suffix(')')?)),
+            None => (prefix, None),
+        };
+
+        let is_word = |s: &str| {
+            !s.is_empty()
+                && s.bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+        };
+        if !is_word(kind) || description.is_empty() || scope.is_some_and(|scope| scope.is_empty()) {
+            return None;
+        }
+
+        let footer = lines.any(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        });
+
+        Some(Self {
+            kind,
+            scope,
+            breaking: bang || footer,
+            description,
+        })
+    }
+
+    /// Returns the type, e.g. `"feat"`.
+    pub const fn kind(&self) -> &'a str {
+        self.kind
+    }
+
+    /// Returns the scope, e.g. `"parser"` in `fix(parser): ...`.
+    pub const fn scope(&self) -> Option<&'a str> {
+        self.scope
+    }
+
+    /// Returns `true` if the commit is marked with `!` or has a breaking change footer.
+    pub const fn is_breaking(&self) -> bool {
+        self.breaking
+    }
+
+    /// Returns the description after the colon.
+    pub const fn description(&self) -> &'a str {
+        self.description
+    }
+
+    /// Returns the bump this commit needs on its own, ignoring the 0.x rule.
+    ///
+    /// # Returns
+    /// The `BumpLevel`, or `None` if the commit does not need a release.
+    pub fn bump_level(&self) -> Option<BumpLevel> {
+        if self.breaking {
+            Some(BumpLevel::Major)
+        } else if self.kind.eq_ignore_ascii_case("feat") {
+            Some(BumpLevel::Minor)
+        } else if self.kind.eq_ignore_ascii_case("fix") {
+            Some(BumpLevel::Patch)
+        } else {
+            None
+        }
+    }
+}
+
+/// A recommended bump and the version it leads to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Recommendation {
+    level: BumpLevel,
+    version: Version,
+}
+
+impl Recommendation {
+    /// Returns the component to increment, after applying the 0.x rule.
+    pub const fn level(&self) -> BumpLevel {
+        self.level
+    }
+
+    /// Returns the next version.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+}
+
+/// Recommends the next version from the commits since `current` was released.
+///
+/// # Parameters
+/// - `current`: The latest released version.
+/// - `messages`: The commit messages since that release.
+///
+/// # Returns
+/// `None` if no commit needs a release, the `Recommendation` otherwise, or
+/// `VersionError::Overflow` if the bumped component is already `u16::MAX`.
+pub fn recommend<'a>(
+    current: &Version,
+    messages: impl IntoIterator<Item = &'a str>,
+) -> Result<Option<Recommendation>, VersionError> {
+    let level = messages
+        .into_iter()
+        .filter_map(Commit::parse)
+        .filter_map(|commit| commit.bump_level())
+        .max();
+    let Some(mut level) = level else {
+        return Ok(None);
+    };
+    if level == BumpLevel::Major && current.major() == 0 {
+        level = BumpLevel::Minor;
+    }
+    Ok(Some(Recommendation {
+        level,
+        version: current.bumped(level)?,
+    }))
+}
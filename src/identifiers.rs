@@ -0,0 +1,85 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+/// Fixed-capacity storage for dot-separated SemVer identifiers.
+///
+/// The identifiers are kept inline so that the owning `Version` stays `Copy`
+/// and can be constructed in `const` context.
+#[derive(Clone, Copy)]
+pub(crate) struct Identifiers<const N: usize> {
+    len: u8,
+    bytes: [u8; N],
+}
+
+/// Reasons why a string is not a valid list of identifiers.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum IdentifiersError {
+    Invalid,
+    TooLong,
+}
+
+impl<const N: usize> Identifiers<N> {
+    pub(crate) const EMPTY: Self = Self {
+        len: 0,
+        bytes: [0; N],
+    };
+
+    /// Validates and stores `s`.
+    ///
+    /// Every identifier must be non-empty and consist of ASCII alphanumerics and hyphens.
+    /// When `allow_leading_zeros` is `false`, numeric identifiers must not have leading zeros.
+    pub(crate) const fn parse(
+        s: &[u8],
+        allow_leading_zeros: bool,
+    ) -> Result<Self, IdentifiersError> {
+        if s.is_empty() {
+            return Ok(Self::EMPTY);
+        }
+
+        let mut bytes = [0; N];
+        let mut start = 0;
+        let mut numeric = true;
+        let mut i = 0;
+        while i <= s.len() {
+            if i == s.len() || s[i] == b'.' {
+                let len = i - start;
+                if len == 0 || (!allow_leading_zeros && numeric && len > 1 && s[start] == b'0') {
+                    return Err(IdentifiersError::Invalid);
+                }
+                start = i + 1;
+                numeric = true;
+            } else if s[i].is_ascii_alphanumeric() || s[i] == b'-' {
+                numeric &= s[i].is_ascii_digit();
+            } else {
+                return Err(IdentifiersError::Invalid);
+            }
+            if i < s.len() && i < N {
+                bytes[i] = s[i];
+            }
+            i += 1;
+        }
+        if s.len() > N {
+            return Err(IdentifiersError::TooLong);
+        }
+
+        Ok(Self {
+            len: s.len() as u8,
+            bytes,
+        })
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // Only ASCII is ever stored, so this can not fail.
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or_default()
+    }
+}
+
+pub(crate) fn is_numeric(identifier: &str) -> bool {
+    identifier.bytes().all(|b| b.is_ascii_digit())
+}
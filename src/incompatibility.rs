@@ -0,0 +1,227 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::{Component, Version};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// One of the two peers of a version check, seen from the peer doing the check.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Side {
+    Local,
+    Remote,
+}
+
+/// The role of a peer in a connection.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Role {
+    Client,
+    Server,
+}
+
+/// What a player or operator can do to resolve an [`Incompatibility`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SuggestedAction {
+    UpgradeClient,
+    UpgradeServer,
+}
+
+/// Why two versions are not compatible.
+///
+/// Returned by [`Version::check_compatible`]. It names the first component
+/// that differs and which side is newer, so the older side can be upgraded.
+///
+/// # Examples
+///
+/// ```
+/// use app_version::{Component, Role, SuggestedAction, Version};
+///
+/// let client = Version::new(1, 4, 0);
+/// let err = client.check_compatible(&Version::new(2, 0, 0), Role::Client).unwrap_err();
+/// assert_eq!(err.component(), Some(Component::Major));
+/// assert_eq!(err.suggested_action(), Some(SuggestedAction::UpgradeClient));
+/// assert_eq!(
+///     err.to_string(),
+///     "The server has a newer major version than the client. Please upgrade the client."
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Incompatibility {
+    local_role: Role,
+    difference: Option<(Component, Side)>,
+}
+
+impl Side {
+    /// Returns the other side.
+    pub const fn opposite(self) -> Self {
+        match self {
+            Side::Local => Side::Remote,
+            Side::Remote => Side::Local,
+        }
+    }
+}
+
+impl Role {
+    /// Returns the other role.
+    pub const fn opposite(self) -> Self {
+        match self {
+            Role::Client => Role::Server,
+            Role::Server => Role::Client,
+        }
+    }
+}
+
+const COMPONENT_MASK: u8 = 0b0_0111;
+const REMOTE_NEWER: u8 = 0b0_1000;
+const LOCAL_IS_SERVER: u8 = 0b1_0000;
+
+impl Incompatibility {
+    /// Describes why `local` and `remote` were found incompatible.
+    ///
+    /// The difference is the first component, in precedence order, where the versions differ.
+    pub(crate) fn between(local: &Version, remote: &Version, local_role: Role) -> Self {
+        let difference = [
+            (Component::Major, local.major().cmp(&remote.major())),
+            (Component::Minor, local.minor().cmp(&remote.minor())),
+            (Component::Patch, local.patch().cmp(&remote.patch())),
+            (
+                Component::Prerelease,
+                local.prerelease().cmp(remote.prerelease()),
+            ),
+        ]
+        .into_iter()
+        .find_map(|(component, ordering)| match ordering {
+            Ordering::Less => Some((component, Side::Remote)),
+            Ordering::Greater => Some((component, Side::Local)),
+            Ordering::Equal => None,
+        });
+        Self {
+            local_role,
+            difference,
+        }
+    }
+
+    /// Returns the role of the local side.
+    pub const fn local_role(&self) -> Role {
+        self.local_role
+    }
+
+    /// Returns the first component that differs, or `None` if the versions have
+    /// equal precedence but were still rejected by the policy.
+    pub const fn component(&self) -> Option<Component> {
+        match self.difference {
+            Some((component, _)) => Some(component),
+            None => None,
+        }
+    }
+
+    /// Returns the side with the newer version, or `None` if neither is newer.
+    pub const fn newer_side(&self) -> Option<Side> {
+        match self.difference {
+            Some((_, side)) => Some(side),
+            None => None,
+        }
+    }
+
+    /// Returns the role of the side with the newer version, or `None` if neither is newer.
+    pub const fn newer_role(&self) -> Option<Role> {
+        match self.difference {
+            Some((_, Side::Local)) => Some(self.local_role),
+            Some((_, Side::Remote)) => Some(self.local_role.opposite()),
+            None => None,
+        }
+    }
+
+    /// Suggests upgrading the side with the older version.
+    pub const fn suggested_action(&self) -> Option<SuggestedAction> {
+        match self.newer_role() {
+            Some(Role::Server) => Some(SuggestedAction::UpgradeClient),
+            Some(Role::Client) => Some(SuggestedAction::UpgradeServer),
+            None => None,
+        }
+    }
+
+    /// Returns the same incompatibility as seen from the remote side.
+    pub const fn reversed(&self) -> Self {
+        Self {
+            local_role: self.local_role.opposite(),
+            difference: match self.difference {
+                Some((component, side)) => Some((component, side.opposite())),
+                None => None,
+            },
+        }
+    }
+
+    /// Encodes the incompatibility into a single byte for sending over the wire.
+    ///
+    /// Bits 0-2 hold the component (0 for none, then major, minor, patch and
+    /// pre-release as 1 to 4), bit 3 is set if the remote side is newer and
+    /// bit 4 is set if the local side is the server.
+    ///
+    /// The code describes the sender's view; the receiver should call
+    /// [`Incompatibility::reversed`] on the decoded value.
+    pub const fn code(&self) -> u8 {
+        let mut code = match self.difference {
+            Some((component, Side::Local)) => component.code(),
+            Some((component, Side::Remote)) => component.code() | REMOTE_NEWER,
+            None => 0,
+        };
+        if matches!(self.local_role, Role::Server) {
+            code |= LOCAL_IS_SERVER;
+        }
+        code
+    }
+
+    /// Decodes an incompatibility created by [`Incompatibility::code`].
+    ///
+    /// # Returns
+    /// The `Incompatibility`, or `None` if the code is not valid.
+    pub const fn from_code(code: u8) -> Option<Self> {
+        if code & !(COMPONENT_MASK | REMOTE_NEWER | LOCAL_IS_SERVER) != 0 {
+            return None;
+        }
+        let Some(component) = Component::from_code(code & COMPONENT_MASK) else {
+            return None;
+        };
+        let difference = match component {
+            Some(component) if code & REMOTE_NEWER != 0 => Some((component, Side::Remote)),
+            Some(component) => Some((component, Side::Local)),
+            None if code & REMOTE_NEWER != 0 => return None,
+            None => None,
+        };
+        let local_role = if code & LOCAL_IS_SERVER != 0 {
+            Role::Server
+        } else {
+            Role::Client
+        };
+        Some(Self {
+            local_role,
+            difference,
+        })
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Role::Client => write!(f, "client"),
+            Role::Server => write!(f, "server"),
+        }
+    }
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.component(), self.newer_role()) {
+            (Some(component), Some(newer)) => write!(
+                f,
+                "The {newer} has a newer {component} version than the {older}. Please upgrade the {older}.",
+                older = newer.opposite()
+            ),
+            _ => write!(f, "The client and server versions are not compatible."),
+        }
+    }
+}
+
+impl std::error::Error for Incompatibility {}
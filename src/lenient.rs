@@ -0,0 +1,203 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::parse::{parse_number, parse_suffix};
+use crate::{BuildMetadata, Component, Prerelease, Version, VersionError};
+use std::fmt;
+
+/// Controls which coercions [`Version::parse_lenient`] is allowed to make.
+///
+/// The default allows everything except discarding trailing characters.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct LenientOptions {
+    /// Remove leading and trailing whitespace.
+    pub trim: bool,
+    /// Remove a leading `v` or `V`, as in `v1.2.3`.
+    pub allow_v_prefix: bool,
+    /// Default a missing minor or patch version to 0, as in `1.2`.
+    pub fill_missing: bool,
+    /// Ignore anything after the version that can not be parsed, as in `1.2.3 (beta)`.
+    pub allow_trailing: bool,
+}
+
+impl Default for LenientOptions {
+    fn default() -> Self {
+        Self {
+            trim: true,
+            allow_v_prefix: true,
+            fill_missing: true,
+            allow_trailing: false,
+        }
+    }
+}
+
+/// A change [`Version::parse_lenient`] made to get a valid version.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Coercion {
+    /// Surrounding whitespace was removed.
+    Trimmed,
+    /// The given `v` or `V` prefix was removed.
+    StrippedPrefix(char),
+    /// The component was missing and defaulted to 0.
+    Defaulted(Component),
+    /// The given trailing characters were ignored.
+    DiscardedTrailing(String),
+}
+
+impl fmt::Display for Coercion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Coercion::Trimmed => write!(f, "removed surrounding whitespace"),
+            Coercion::StrippedPrefix(prefix) => write!(f, "removed the '{}' prefix", prefix),
+            Coercion::Defaulted(component) => {
+                write!(f, "the missing {} version defaulted to 0", component)
+            }
+            Coercion::DiscardedTrailing(text) => write!(f, "ignored trailing {:?}", text),
+        }
+    }
+}
+
+/// The result of [`Version::parse_lenient`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LenientVersion {
+    version: Version,
+    coercions: Vec<Coercion>,
+}
+
+impl LenientVersion {
+    /// Returns the parsed version.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns the coercions that were made, in the order they were applied.
+    pub fn coercions(&self) -> &[Coercion] {
+        &self.coercions
+    }
+
+    /// Returns `true` if the input was already a valid version string.
+    pub fn is_exact(&self) -> bool {
+        self.coercions.is_empty()
+    }
+}
+
+impl Version {
+    /// Parses a user-entered version string, such as `v1.2` or `V1.2.3 `.
+    ///
+    /// Unlike `FromStr`, this accepts input that is close to a version and reports what
+    /// had to be changed, so tools can warn about it.
+    ///
+    /// # Parameters
+    /// - `s`: The version string.
+    /// - `options`: Which coercions are allowed.
+    ///
+    /// # Returns
+    /// A `LenientVersion` with the version and the coercions made, or a `VersionError`
+    /// if the input can not be coerced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use app_version::{Coercion, Component, LenientOptions, Version};
+    ///
+    /// let parsed = Version::parse_lenient("v1.2", &LenientOptions::default()).unwrap();
+    /// assert_eq!(parsed.version(), Version::new(1, 2, 0));
+    /// assert_eq!(
+    ///     parsed.coercions(),
+    ///     [Coercion::StrippedPrefix('v'), Coercion::Defaulted(Component::Patch)]
+    /// );
+    /// ```
+    pub fn parse_lenient(
+        s: &str,
+        options: &LenientOptions,
+    ) -> Result<LenientVersion, VersionError> {
+        let mut coercions = Vec::new();
+        let mut offset = 0;
+        let mut rest = s;
+
+        if options.trim {
+            let trimmed = rest.trim();
+            if trimmed.len() != rest.len() {
+                offset = rest.len() - rest.trim_start().len();
+                rest = trimmed;
+                coercions.push(Coercion::Trimmed);
+            }
+        }
+
+        if options.allow_v_prefix {
+            if let Some(prefix @ ('v' | 'V')) = rest.chars().next() {
+                rest = &rest[1..];
+                offset += 1;
+                coercions.push(Coercion::StrippedPrefix(prefix));
+            }
+        }
+
+        let components = [Component::Major, Component::Minor, Component::Patch];
+        let mut numbers = [0; 3];
+        let mut found = 0;
+        while found < components.len() {
+            let digits = if found == 0 {
+                rest
+            } else {
+                match rest.strip_prefix('.') {
+                    Some(after_dot) if after_dot.starts_with(|c: char| c.is_ascii_digit()) => {
+                        rest = after_dot;
+                        offset += 1;
+                        after_dot
+                    }
+                    _ => break,
+                }
+            };
+            let end = digits
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(digits.len());
+            let text = if end == 0 {
+                // Report the whole malformed component, not an empty string.
+                &digits[..digits.find('.').unwrap_or(digits.len())]
+            } else {
+                &digits[..end]
+            };
+            numbers[found] = parse_number(text, components[found], offset)?;
+            rest = &rest[end..];
+            offset += end;
+            found += 1;
+        }
+
+        if found < components.len() {
+            if !options.fill_missing {
+                return Err(VersionError::InvalidComponentCount {
+                    expected: components.len(),
+                    found,
+                });
+            }
+            coercions.extend(components[found..].iter().copied().map(Coercion::Defaulted));
+        }
+
+        let suffix = if rest.is_empty() || rest.starts_with(['-', '+']) {
+            parse_suffix(rest.as_bytes()).map_err(Some)
+        } else {
+            Err(None)
+        };
+        let (pre, build) = match suffix {
+            Ok(suffix) => suffix,
+            Err(_) if options.allow_trailing => {
+                coercions.push(Coercion::DiscardedTrailing(rest.to_string()));
+                (Prerelease::EMPTY, BuildMetadata::EMPTY)
+            }
+            Err(err) => {
+                return Err(err.unwrap_or(VersionError::TrailingCharacters {
+                    offset,
+                    text: rest.to_string(),
+                }))
+            }
+        };
+
+        Ok(LenientVersion {
+            version: Version::new(numbers[0], numbers[1], numbers[2])
+                .with_prerelease(pre)
+                .with_build(build),
+            coercions,
+        })
+    }
+}
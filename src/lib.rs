@@ -2,13 +2,57 @@
  * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
  * Licensed under the MIT License. See LICENSE in the project root for license information.
  */
+mod build_metadata;
+mod bump;
+pub mod calver;
+mod compat;
+mod component;
+mod const_parse;
+pub mod conventional;
+mod identifiers;
+mod incompatibility;
+mod lenient;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+pub mod negotiate;
+pub mod npm;
+mod parse;
+mod prerelease;
+mod req;
+#[cfg(feature = "serde")]
+mod serde;
+mod set;
+mod strict;
+pub mod varint;
+pub mod wire;
+#[cfg(feature = "manifest")]
+pub mod workspace;
+
+#[cfg(feature = "derive")]
+pub use app_version_derive::VersionProvider;
+pub use build_metadata::BuildMetadata;
+pub use bump::BumpLevel;
+pub use compat::{
+    AtLeast, CargoCaret, CompatibilityPolicy, Exact, ReadCompatibility, SameMajor, SameMinor,
+};
+pub use component::Component;
+pub use incompatibility::{Incompatibility, Role, Side, SuggestedAction};
+pub use lenient::{Coercion, LenientOptions, LenientVersion};
+pub use prerelease::Prerelease;
+pub use req::{Comparator, Op, VersionReq};
+pub use set::VersionSet;
+pub use varint::DecodeError;
+
+use std::cmp::Ordering;
 use std::fmt;
-use std::num::ParseIntError;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
 /// A struct representing a semantic version.
 ///
-/// This struct contains three components of a version: major, minor, and patch.
+/// This struct contains three components of a version: major, minor, and patch,
+/// an optional pre-release, e.g. `rc.1` in `1.2.0-rc.1`, and optional build
+/// metadata, e.g. `sha.9f3c1a` in `1.2.0+sha.9f3c1a`.
 /// It derives common traits for easy comparison and manipulation.
 ///
 /// # Examples
@@ -24,6 +68,16 @@ use std::str::FromStr;
 /// assert_eq!(version.patch(), 0);
 /// ```
 ///
+/// Creating a pre-release version:
+///
+/// ```
+/// use app_version::{Prerelease, Version};
+///
+/// let beta = Version::new(1,2,0).with_prerelease(Prerelease::new("beta.1").unwrap());
+/// assert_eq!(beta.to_string(), "1.2.0-beta.1");
+/// assert!(beta < Version::new(1,2,0));
+/// ```
+///
 /// ## Comparison
 ///
 /// Versions can be compared for equality:
@@ -35,11 +89,27 @@ use std::str::FromStr;
 /// let version2 = Version::new(1,0,1);
 /// assert_ne!(version1, version2);
 /// ```
+///
+/// ## Ordering
+///
+/// Versions are ordered according to SemVer 2.0 precedence. Build metadata is
+/// only used as a final tie-breaker, so that the ordering agrees with equality;
+/// use [`Version::cmp_precedence`] to ignore it entirely.
+///
+/// ```
+/// use app_version::Version;
+///
+/// let mut versions = vec![Version::new(1,10,0), Version::new(1,2,0), Version::new(0,9,9)];
+/// versions.sort();
+/// assert_eq!(versions, [Version::new(0,9,9), Version::new(1,2,0), Version::new(1,10,0)]);
+/// ```
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
 pub struct Version {
     major: u16,
     minor: u16,
     patch: u16,
+    pre: Prerelease,
+    build: BuildMetadata,
 }
 
 impl Version {
@@ -57,9 +127,33 @@ impl Version {
             major,
             minor,
             patch,
+            pre: Prerelease::EMPTY,
+            build: BuildMetadata::EMPTY,
         }
     }
 
+    /// Returns a copy of this version with the given pre-release.
+    ///
+    /// # Parameters
+    /// - `pre`: The pre-release identifiers.
+    ///
+    /// # Returns
+    /// A `Version` instance.
+    pub const fn with_prerelease(self, pre: Prerelease) -> Self {
+        Self { pre, ..self }
+    }
+
+    /// Returns a copy of this version with the given build metadata.
+    ///
+    /// # Parameters
+    /// - `build`: The build metadata identifiers.
+    ///
+    /// # Returns
+    /// A `Version` instance.
+    pub const fn with_build(self, build: BuildMetadata) -> Self {
+        Self { build, ..self }
+    }
+
     /// Returns the major version number.
     pub const fn major(&self) -> u16 {
         self.major
@@ -75,40 +169,259 @@ impl Version {
         self.patch
     }
 
-    /// Increments the patch version.
+    /// Returns the pre-release, which is empty for normal releases.
+    pub const fn prerelease(&self) -> &Prerelease {
+        &self.pre
+    }
+
+    /// Returns the build metadata, which is empty if none was given.
+    pub const fn build(&self) -> &BuildMetadata {
+        &self.build
+    }
+
+    /// Returns `true` if this is a pre-release version.
+    pub const fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Increments the patch version and clears the pre-release and build metadata.
+    ///
+    /// # Panics
+    /// If patch is already `u16::MAX`. Use [`Version::checked_increment_patch`] to
+    /// handle overflow instead.
     pub fn increment_patch(&mut self) {
-        self.patch += 1;
+        *self = self
+            .checked_increment_patch()
+            .expect("patch version is already u16::MAX");
     }
 
-    /// Increments the minor version and resets patch to 0.
+    /// Increments the minor version, resets patch to 0 and clears the pre-release and build metadata.
+    ///
+    /// # Panics
+    /// If minor is already `u16::MAX`. Use [`Version::checked_increment_minor`] to
+    /// handle overflow instead.
     pub fn increment_minor(&mut self) {
-        self.minor += 1;
-        self.patch = 0;
+        *self = self
+            .checked_increment_minor()
+            .expect("minor version is already u16::MAX");
     }
 
-    /// Increments the major version and resets minor and patch to 0.
+    /// Increments the major version, resets minor and patch to 0 and clears the pre-release
+    /// and build metadata.
+    ///
+    /// # Panics
+    /// If major is already `u16::MAX`. Use [`Version::checked_increment_major`] to
+    /// handle overflow instead.
     pub fn increment_major(&mut self) {
-        self.major += 1;
-        self.minor = 0;
-        self.patch = 0;
+        *self = self
+            .checked_increment_major()
+            .expect("major version is already u16::MAX");
     }
 
     /// Checks if the current version is compatible with another version.
     ///
+    /// The versions are compatible if the major versions are equal, see [`SameMajor`].
+    /// Build metadata is ignored.
+    ///
     /// # Parameters
     /// - `other`: The other version to check compatibility against.
     ///
     /// # Returns
     /// `true` if the versions are compatible, otherwise `false`.
     pub fn is_compatible(&self, other: &Self) -> bool {
-        self.major == other.major
+        self.is_compatible_with(other, &SameMajor)
+    }
+
+    /// Checks if the current version is compatible with another version under a policy.
+    ///
+    /// # Parameters
+    /// - `other`: The other version to check compatibility against.
+    /// - `policy`: The rule deciding compatibility.
+    ///
+    /// # Returns
+    /// `true` if the versions are compatible, otherwise `false`.
+    pub fn is_compatible_with<P: CompatibilityPolicy + ?Sized>(
+        &self,
+        other: &Self,
+        policy: &P,
+    ) -> bool {
+        policy.is_compatible(self, other)
+    }
+
+    /// Checks if a remote version is compatible, explaining why not.
+    ///
+    /// The versions are compatible if the major versions are equal, see [`SameMajor`].
+    ///
+    /// # Parameters
+    /// - `remote`: The version of the remote peer.
+    /// - `local_role`: Whether this side is the client or the server.
+    ///
+    /// # Returns
+    /// An `Incompatibility` naming the differing component and the newer side.
+    pub fn check_compatible(&self, remote: &Self, local_role: Role) -> Result<(), Incompatibility> {
+        self.check_compatible_with(remote, local_role, &SameMajor)
+    }
+
+    /// Checks if a remote version is compatible under a policy, explaining why not.
+    ///
+    /// # Parameters
+    /// - `remote`: The version of the remote peer.
+    /// - `local_role`: Whether this side is the client or the server.
+    /// - `policy`: The rule deciding compatibility.
+    ///
+    /// # Returns
+    /// An `Incompatibility` naming the differing component and the newer side.
+    pub fn check_compatible_with<P: CompatibilityPolicy + ?Sized>(
+        &self,
+        remote: &Self,
+        local_role: Role,
+        policy: &P,
+    ) -> Result<(), Incompatibility> {
+        if policy.is_compatible(self, remote) {
+            Ok(())
+        } else {
+            Err(Incompatibility::between(self, remote, local_role))
+        }
+    }
+
+    /// Checks if this version can read data, such as a save file, written by another version.
+    ///
+    /// The data is readable if the policy considers the versions compatible, with this
+    /// version as `local` and the writer as `remote`, so a closure can express
+    /// directional rules such as `|reader, writer| writer <= reader`. Otherwise the
+    /// result names the side that fails the policy on its own, such as a version below
+    /// the [`AtLeast`] minimum, or else the older side.
+    ///
+    /// # Parameters
+    /// - `writer`: The version that wrote the data.
+    /// - `policy`: The rule deciding compatibility.
+    ///
+    /// # Returns
+    /// A `ReadCompatibility` telling which side, if any, is too old.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use app_version::{ReadCompatibility, SameMajor, SameMinor, Version};
+    ///
+    /// let server = Version::new(1, 4, 0);
+    /// assert!(server.can_read_data_from(&Version::new(1, 2, 0), &SameMajor).is_compatible());
+    /// assert_eq!(
+    ///     Version::new(1, 2, 0).can_read_data_from(&server, &SameMinor),
+    ///     ReadCompatibility::ReaderTooOld
+    /// );
+    /// ```
+    pub fn can_read_data_from<P: CompatibilityPolicy + ?Sized>(
+        &self,
+        writer: &Self,
+        policy: &P,
+    ) -> ReadCompatibility {
+        ReadCompatibility::check(self, writer, policy)
+    }
+
+    /// Checks if data written by this version can be read by another version.
+    ///
+    /// This is [`Version::can_read_data_from`] seen from the writer's side.
+    ///
+    /// # Parameters
+    /// - `reader`: The version that reads the data.
+    /// - `policy`: The rule deciding compatibility.
+    ///
+    /// # Returns
+    /// A `ReadCompatibility` telling which side, if any, is too old.
+    pub fn can_be_read_by<P: CompatibilityPolicy + ?Sized>(
+        &self,
+        reader: &Self,
+        policy: &P,
+    ) -> ReadCompatibility {
+        ReadCompatibility::check(reader, self, policy)
+    }
+
+    /// Compares two versions according to SemVer 2.0 precedence.
+    ///
+    /// Major, minor and patch are compared numerically, in that order.
+    /// A pre-release has lower precedence than the release it precedes, and
+    /// pre-releases are compared identifier by identifier. Build metadata is ignored.
+    ///
+    /// # Parameters
+    /// - `other`: The version to compare against.
+    ///
+    /// # Returns
+    /// The `Ordering` of `self` relative to `other`.
+    pub fn cmp_precedence(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then(self.minor.cmp(&other.minor))
+            .then(self.patch.cmp(&other.patch))
+            .then_with(|| self.pre.cmp(&other.pre))
+    }
+
+    /// Returns the version with the highest precedence.
+    ///
+    /// # Parameters
+    /// - `versions`: The versions to select from.
+    ///
+    /// # Returns
+    /// The highest version, or `None` if `versions` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use app_version::Version;
+    ///
+    /// let advertised = [Version::new(1, 2, 0), Version::new(1, 4, 1), Version::new(1, 3, 9)];
+    /// assert_eq!(Version::max_of(advertised), Some(Version::new(1, 4, 1)));
+    /// ```
+    pub fn max_of<I: IntoIterator<Item = Version>>(versions: I) -> Option<Version> {
+        versions.into_iter().max()
+    }
+
+    /// Returns the version with the lowest precedence.
+    ///
+    /// # Parameters
+    /// - `versions`: The versions to select from.
+    ///
+    /// # Returns
+    /// The lowest version, or `None` if `versions` is empty.
+    pub fn min_of<I: IntoIterator<Item = Version>>(versions: I) -> Option<Version> {
+        versions.into_iter().min()
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_precedence(other)
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.major.hash(state);
+        self.minor.hash(state);
+        self.patch.hash(state);
+        self.pre.hash(state);
+        self.build.hash(state);
     }
 }
 
 // Implement the `fmt::Display` trait for `Version`
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre)?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build)?;
+        }
+        Ok(())
     }
 }
 
@@ -118,23 +431,114 @@ impl From<(u16, u16, u16)> for Version {
     }
 }
 
-#[derive(Debug)]
-pub enum VersionError {
-    ParseIntError(ParseIntError),
-    InvalidFormat,
+/// Why a numeric version component could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberErrorKind {
+    /// The component was empty, as in `1..3`.
+    Empty,
+    /// The component contained something other than ASCII digits.
+    InvalidDigit,
+    /// The component does not fit in a `u16`.
+    Overflow,
+    /// The component has a leading zero, which SemVer does not allow.
+    LeadingZero,
 }
 
-impl From<ParseIntError> for VersionError {
-    fn from(error: ParseIntError) -> Self {
-        VersionError::ParseIntError(error)
+impl fmt::Display for NumberErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberErrorKind::Empty => write!(f, "it is empty"),
+            NumberErrorKind::InvalidDigit => write!(f, "it is not a number"),
+            NumberErrorKind::Overflow => write!(f, "it is larger than {}", u16::MAX),
+            NumberErrorKind::LeadingZero => write!(f, "it has a leading zero"),
+        }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionError {
+    /// The version core did not have exactly `expected` dot-separated components.
+    InvalidComponentCount { expected: usize, found: usize },
+    /// A major, minor or patch component was not a valid `u16`.
+    ///
+    /// `offset` is the byte offset of `text` in the parsed string.
+    InvalidNumber {
+        component: Component,
+        offset: usize,
+        text: String,
+        kind: NumberErrorKind,
+    },
+    /// Unexpected characters followed the version, starting at byte `offset`.
+    TrailingCharacters { offset: usize, text: String },
+    /// A pre-release identifier was empty, had a leading zero or an invalid character.
+    InvalidPrerelease,
+    /// The pre-release did not fit in [`Prerelease::MAX_LEN`] bytes.
+    PrereleaseTooLong,
+    /// A build metadata identifier was empty or had an invalid character.
+    InvalidBuildMetadata,
+    /// The build metadata did not fit in [`BuildMetadata::MAX_LEN`] bytes.
+    BuildMetadataTooLong,
+    /// A Cargo-style [`VersionReq`] could not be parsed.
+    InvalidRequirement,
+    /// An npm-style range could not be parsed.
+    InvalidRange,
+    /// A calendar version format string had an unknown or misplaced token.
+    InvalidCalVerFormat,
+    /// A calendar version did not match its format.
+    InvalidCalVer,
+    /// A calendar version did not name a valid date.
+    InvalidDate,
+    /// The output buffer was too small for the encoded version.
+    BufferTooSmall,
+    /// A binary encoded version could not be decoded.
+    Decode(DecodeError),
+    /// Incrementing the component would overflow `u16::MAX`.
+    Overflow(Component),
+}
+
 impl fmt::Display for VersionError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            VersionError::InvalidFormat => write!(f, "Invalid version format"),
-            VersionError::ParseIntError(err) => write!(f, "Parse error: {}", err),
+            VersionError::InvalidComponentCount { expected, found } => write!(
+                f,
+                "Invalid version format: expected {} components, found {}",
+                expected, found
+            ),
+            VersionError::InvalidNumber {
+                component,
+                offset,
+                text,
+                kind,
+            } => write!(
+                f,
+                "Invalid {} version {:?} at byte {}: {}",
+                component, text, offset, kind
+            ),
+            VersionError::TrailingCharacters { offset, text } => write!(
+                f,
+                "Unexpected trailing characters {:?} at byte {}",
+                text, offset
+            ),
+            VersionError::InvalidPrerelease => write!(f, "Invalid pre-release identifier"),
+            VersionError::PrereleaseTooLong => write!(
+                f,
+                "Pre-release is longer than {} bytes",
+                Prerelease::MAX_LEN
+            ),
+            VersionError::InvalidBuildMetadata => write!(f, "Invalid build metadata identifier"),
+            VersionError::BuildMetadataTooLong => write!(
+                f,
+                "Build metadata is longer than {} bytes",
+                BuildMetadata::MAX_LEN
+            ),
+            VersionError::InvalidRequirement => write!(f, "Invalid version requirement"),
+            VersionError::InvalidRange => write!(f, "Invalid version range"),
+            VersionError::InvalidCalVerFormat => write!(f, "Invalid calendar version format"),
+            VersionError::InvalidCalVer => write!(f, "Invalid calendar version"),
+            VersionError::InvalidDate => write!(f, "Invalid date in calendar version"),
+            VersionError::BufferTooSmall => write!(f, "Buffer is too small"),
+            VersionError::Decode(err) => write!(f, "Decode error: {}", err),
+            VersionError::Overflow(component) => write!(f, "The {} version overflowed", component),
         }
     }
 }
@@ -145,19 +549,30 @@ impl FromStr for Version {
     type Err = VersionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split('.').collect();
-        if parts.len() != 3 {
-            return Err(VersionError::InvalidFormat);
-        }
-
-        let major = parts[0].parse::<u16>()?;
-        let minor = parts[1].parse::<u16>()?;
-        let patch = parts[2].parse::<u16>()?;
-
-        Ok(Version::new(major, minor, patch))
+        Self::parse_bytes(s.as_bytes())
     }
 }
 
+/// Returns the version of the enclosing package, from `CARGO_PKG_VERSION`, as a `Version`.
+///
+/// The version is parsed at compile time, so an invalid version in `Cargo.toml`
+/// fails the build instead of failing at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use app_version::{package_version, Version};
+///
+/// const VERSION: Version = package_version!();
+/// assert_eq!(VERSION.to_string(), env!("CARGO_PKG_VERSION"));
+/// ```
+#[macro_export]
+macro_rules! package_version {
+    () => {
+        const { $crate::Version::parse_const(env!("CARGO_PKG_VERSION")) }
+    };
+}
+
 /// A trait that provides a version.
 ///
 /// Implementers of this trait must define how to return the version
@@ -182,7 +597,20 @@ impl FromStr for Version {
 /// let my_version = MySoftware::version();
 /// assert_eq!(my_version, Version::new(1, 0, 0 ));
 /// ```
-
+///
+/// Use [`package_version!`] to return the version from `Cargo.toml`:
+///
+/// ```
+/// use app_version::{package_version, Version, VersionProvider};
+///
+/// struct MySoftware;
+///
+/// impl VersionProvider for MySoftware {
+///     fn version() -> Version {
+///         package_version!()
+///     }
+/// }
+/// ```
 pub trait VersionProvider {
     fn version() -> Version;
 }
@@ -0,0 +1,319 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Reading and writing the version in `Cargo.toml` manifests.
+//!
+//! Edits keep the formatting and comments of the rest of the file. A package that
+//! uses `version.workspace = true` gets its version from `[workspace.package]` in
+//! the workspace root, which is found the same way Cargo finds it.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use app_version::{manifest, BumpLevel};
+//!
+//! let change = manifest::bump("Cargo.toml", BumpLevel::Minor)?;
+//! println!("{} -> {} in {}", change.previous, change.version, change.path.display());
+//! # Ok::<(), app_version::manifest::ManifestError>(())
+//! ```
+use crate::{BumpLevel, Version, VersionError};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, TomlError, Value};
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(io::Error),
+    Toml(TomlError),
+    Version(VersionError),
+    /// The manifest has no version where one was expected.
+    MissingVersion,
+    /// The version is inherited from the workspace, and must be changed there.
+    InheritedVersion,
+    /// No workspace root was found for a package that inherits its version.
+    WorkspaceNotFound,
+    /// The workspace has no member with this package name.
+    UnknownPackage(String),
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::Io(err) => write!(f, "I/O error: {}", err),
+            ManifestError::Toml(err) => write!(f, "Invalid manifest: {}", err),
+            ManifestError::Version(err) => write!(f, "{}", err),
+            ManifestError::MissingVersion => write!(f, "The manifest has no version"),
+            ManifestError::InheritedVersion => {
+                write!(f, "The version is inherited from the workspace")
+            }
+            ManifestError::WorkspaceNotFound => write!(f, "No workspace root was found"),
+            ManifestError::UnknownPackage(name) => {
+                write!(f, "The workspace has no package named '{}'", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ManifestError::Io(err) => Some(err),
+            ManifestError::Toml(err) => Some(err),
+            ManifestError::Version(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ManifestError {
+    fn from(error: io::Error) -> Self {
+        ManifestError::Io(error)
+    }
+}
+
+impl From<TomlError> for ManifestError {
+    fn from(error: TomlError) -> Self {
+        ManifestError::Toml(error)
+    }
+}
+
+impl From<VersionError> for ManifestError {
+    fn from(error: VersionError) -> Self {
+        ManifestError::Version(error)
+    }
+}
+
+/// A `Cargo.toml` file, loaded for editing.
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    path: PathBuf,
+    document: DocumentMut,
+}
+
+impl Manifest {
+    /// Loads a manifest.
+    ///
+    /// # Parameters
+    /// - `path`: The path of the `Cargo.toml` file.
+    ///
+    /// # Returns
+    /// The `Manifest`, or a `ManifestError` if it can not be read or parsed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        let path = path.as_ref().to_path_buf();
+        let document = fs::read_to_string(&path)?.parse()?;
+        Ok(Self { path, document })
+    }
+
+    /// Writes the manifest back to its path.
+    pub fn save(&self) -> Result<(), ManifestError> {
+        fs::write(&self.path, self.document.to_string())?;
+        Ok(())
+    }
+
+    /// Returns the path the manifest was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the package name, or `None` for a virtual workspace manifest.
+    pub fn name(&self) -> Option<&str> {
+        self.document.get("package")?.get("name")?.as_str()
+    }
+
+    /// Returns `true` if the manifest has a `[workspace]` table.
+    pub fn is_workspace_root(&self) -> bool {
+        self.document.contains_key("workspace")
+    }
+
+    /// Returns `true` if the package uses `version.workspace = true`.
+    pub fn is_version_inherited(&self) -> bool {
+        self.document
+            .get("package")
+            .and_then(|package| package.get("version"))
+            .and_then(|version| version.get("workspace"))
+            .and_then(Item::as_bool)
+            .unwrap_or(false)
+    }
+
+    /// Returns `[package].version`.
+    ///
+    /// # Returns
+    /// The `Version`, or `ManifestError::InheritedVersion` if the version comes from the
+    /// workspace. Use [`read_version`] to resolve it.
+    pub fn version(&self) -> Result<Version, ManifestError> {
+        if self.is_version_inherited() {
+            return Err(ManifestError::InheritedVersion);
+        }
+        parse_version(
+            self.document
+                .get("package")
+                .and_then(|package| package.get("version")),
+        )
+    }
+
+    /// Sets `[package].version`, keeping its formatting.
+    pub fn set_version(&mut self, version: &Version) -> Result<(), ManifestError> {
+        if self.is_version_inherited() {
+            return Err(ManifestError::InheritedVersion);
+        }
+        let item = self
+            .document
+            .get_mut("package")
+            .and_then(|package| package.get_mut("version"));
+        replace_string(item, &version.to_string())
+    }
+
+    /// Returns `[workspace.package].version`.
+    pub fn workspace_version(&self) -> Result<Version, ManifestError> {
+        parse_version(
+            self.document
+                .get("workspace")
+                .and_then(|workspace| workspace.get("package"))
+                .and_then(|package| package.get("version")),
+        )
+    }
+
+    /// Sets `[workspace.package].version`, keeping its formatting.
+    pub fn set_workspace_version(&mut self, version: &Version) -> Result<(), ManifestError> {
+        let item = self
+            .document
+            .get_mut("workspace")
+            .and_then(|workspace| workspace.get_mut("package"))
+            .and_then(|package| package.get_mut("version"));
+        replace_string(item, &version.to_string())
+    }
+
+    /// Finds the manifest of the workspace this package belongs to.
+    ///
+    /// Like Cargo, this uses `[package].workspace` if it is set, and otherwise
+    /// searches this and the parent directories for a manifest with a `[workspace]`.
+    pub fn find_workspace_root(&self) -> Result<Manifest, ManifestError> {
+        if self.is_workspace_root() {
+            return Ok(self.clone());
+        }
+        let path = fs::canonicalize(&self.path)?;
+        let dir = path.parent().ok_or(ManifestError::WorkspaceNotFound)?;
+
+        let explicit = self
+            .document
+            .get("package")
+            .and_then(|package| package.get("workspace"))
+            .and_then(Item::as_str);
+        if let Some(root) = explicit {
+            return Manifest::open(dir.join(root).join("Cargo.toml"));
+        }
+
+        for dir in dir.ancestors().skip(1) {
+            let candidate = dir.join("Cargo.toml");
+            if candidate.is_file() {
+                let manifest = Manifest::open(candidate)?;
+                if manifest.is_workspace_root() {
+                    return Ok(manifest);
+                }
+            }
+        }
+        Err(ManifestError::WorkspaceNotFound)
+    }
+
+    pub(crate) fn document(&self) -> &DocumentMut {
+        &self.document
+    }
+
+    pub(crate) fn document_mut(&mut self) -> &mut DocumentMut {
+        &mut self.document
+    }
+}
+
+/// A version change written to a manifest.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VersionChange {
+    /// The manifest that was changed, which is the workspace root for inherited versions.
+    pub path: PathBuf,
+    /// The version before the change.
+    pub previous: Version,
+    /// The version that was written.
+    pub version: Version,
+}
+
+/// Reads the version of the package in `path`, resolving `version.workspace = true`.
+pub fn read_version(path: impl AsRef<Path>) -> Result<Version, ManifestError> {
+    let manifest = Manifest::open(path)?;
+    if manifest.is_version_inherited() {
+        manifest.find_workspace_root()?.workspace_version()
+    } else {
+        manifest.version()
+    }
+}
+
+/// Replaces the version of the package in `path` with the result of `change`.
+///
+/// If the version is inherited, the workspace root manifest is changed instead.
+///
+/// # Parameters
+/// - `path`: The path of the package's `Cargo.toml`.
+/// - `change`: Computes the new version from the current one.
+///
+/// # Returns
+/// The `VersionChange` that was written.
+pub fn update(
+    path: impl AsRef<Path>,
+    change: impl FnOnce(&Version) -> Result<Version, VersionError>,
+) -> Result<VersionChange, ManifestError> {
+    let mut manifest = Manifest::open(path)?;
+    let inherited = manifest.is_version_inherited();
+    if inherited {
+        manifest = manifest.find_workspace_root()?;
+    }
+
+    let previous = if inherited {
+        manifest.workspace_version()?
+    } else {
+        manifest.version()?
+    };
+    let version = change(&previous)?;
+    if inherited {
+        manifest.set_workspace_version(&version)?;
+    } else {
+        manifest.set_version(&version)?;
+    }
+    manifest.save()?;
+
+    Ok(VersionChange {
+        path: manifest.path,
+        previous,
+        version,
+    })
+}
+
+/// Increments the version of the package in `path`, see [`Version::bumped`].
+pub fn bump(path: impl AsRef<Path>, level: BumpLevel) -> Result<VersionChange, ManifestError> {
+    update(path, |version| version.bumped(level))
+}
+
+/// Sets the version of the package in `path`.
+pub fn set(path: impl AsRef<Path>, version: &Version) -> Result<VersionChange, ManifestError> {
+    update(path, |_| Ok(*version))
+}
+
+fn parse_version(item: Option<&Item>) -> Result<Version, ManifestError> {
+    let version = item
+        .and_then(Item::as_str)
+        .ok_or(ManifestError::MissingVersion)?;
+    Ok(version.parse()?)
+}
+
+/// Replaces a string value while keeping the whitespace and comments around it.
+pub(crate) fn replace_string(item: Option<&mut Item>, s: &str) -> Result<(), ManifestError> {
+    let value = item
+        .and_then(Item::as_value_mut)
+        .filter(|value| value.is_str())
+        .ok_or(ManifestError::MissingVersion)?;
+    let decor = value.decor().clone();
+    *value = Value::from(s);
+    *value.decor_mut() = decor;
+    Ok(())
+}
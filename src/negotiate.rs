@@ -0,0 +1,376 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Client/server version negotiation.
+//!
+//! Each peer advertises an [`Offer`]: the versions it supports, given as
+//! concrete versions and/or [`VersionReq`] ranges. The client sends its offer,
+//! and the server answers with the highest version that both offers support,
+//! or with a [`Rejection`] telling why there is none.
+//!
+//! Concrete versions named by either offer are the preferred candidates. The
+//! highest one that both offers support is chosen by precedence, with build
+//! metadata as the final tie-breaker. If no named version is supported by both,
+//! the offers are intersected as [`VersionSet`]s, and the highest interval of the
+//! intersection picks its inclusive upper bound, or otherwise its lowest release,
+//! e.g. `1.0.0` when both peers offer `^1`. Either way, both peers arrive at the
+//! same version no matter which side runs [`negotiate`].
+//!
+//! # Wire format
+//!
+//! Versions are length-prefixed [variable-length encodings](crate::varint):
+//! one length byte followed by that many bytes.
+//!
+//! An offer is a count byte followed by that many versions, and a count byte
+//! followed by that many ranges, each a length byte and the UTF-8 requirement
+//! string. An offer can hold at most 255 versions and 255 ranges.
+//!
+//! An answer starts with a tag byte:
+//!
+//! | Tag | Meaning                    | Followed by                                          |
+//! |-----|----------------------------|------------------------------------------------------|
+//! | 0   | accepted                   | the chosen version                                   |
+//! | 1   | client offer is empty      |                                                      |
+//! | 2   | server offer is empty      |                                                      |
+//! | 3   | no common version          | a byte that is 1 if the server's highest version follows, otherwise 0 |
+//!
+//! # Examples
+//!
+//! ```
+//! use app_version::negotiate::{negotiate, Offer};
+//! use app_version::{Version, VersionReq};
+//!
+//! let server = Offer::new()
+//!     .with_range(VersionReq::parse(">=1.2, <2").unwrap());
+//! let client = Offer::new()
+//!     .with_version(Version::new(1, 3, 0))
+//!     .with_version(Version::new(2, 0, 0));
+//! assert_eq!(negotiate(&server, &client), Ok(Version::new(1, 3, 0)));
+//! ```
+use crate::{BuildMetadata, BumpLevel, Prerelease, Version, VersionError, VersionReq, VersionSet};
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::ops::Bound::{Excluded, Included, Unbounded};
+
+/// The versions a peer supports.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Offer {
+    versions: Vec<Version>,
+    ranges: Vec<VersionReq>,
+}
+
+/// Why no version could be agreed on.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Rejection {
+    /// The client did not offer any version.
+    ClientOfferEmpty,
+    /// The server did not offer any version.
+    ServerOfferEmpty,
+    /// No version is supported by both offers.
+    ///
+    /// Holds the highest concrete version the server offered, if it named any,
+    /// so that the client can tell the player which version to install.
+    NoCommonVersion { server_highest: Option<Version> },
+}
+
+/// The server's reply to a client's offer.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Answer {
+    /// Both peers will use this version.
+    Accepted(Version),
+    /// No version could be agreed on.
+    Rejected(Rejection),
+}
+
+impl Offer {
+    /// Creates an empty offer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a supported version.
+    pub fn with_version(mut self, version: Version) -> Self {
+        self.versions.push(version);
+        self
+    }
+
+    /// Adds a range of supported versions.
+    pub fn with_range(mut self, range: VersionReq) -> Self {
+        self.ranges.push(range);
+        self
+    }
+
+    /// Returns the concrete versions in this offer.
+    pub fn versions(&self) -> &[Version] {
+        &self.versions
+    }
+
+    /// Returns the ranges in this offer.
+    pub fn ranges(&self) -> &[VersionReq] {
+        &self.ranges
+    }
+
+    /// Returns `true` if the offer names neither versions nor ranges.
+    pub fn is_empty(&self) -> bool {
+        self.versions.is_empty() && self.ranges.is_empty()
+    }
+
+    /// Checks if a version is supported by this offer.
+    ///
+    /// A listed version supports any version with equal precedence, so build
+    /// metadata is ignored.
+    pub fn supports(&self, version: &Version) -> bool {
+        self.versions
+            .iter()
+            .any(|listed| listed.cmp_precedence(version) == Ordering::Equal)
+            || self.ranges.iter().any(|range| range.matches(version))
+    }
+
+    /// Returns the versions this offer supports as a set, ignoring build metadata.
+    pub fn version_set(&self) -> VersionSet {
+        self.versions
+            .iter()
+            .map(|version| VersionSet::exact(version.with_build(BuildMetadata::EMPTY)))
+            .chain(self.ranges.iter().map(VersionSet::from))
+            .fold(VersionSet::empty(), |set, other| set.union(&other))
+    }
+
+    /// Writes the offer, see the [wire format](self).
+    ///
+    /// # Returns
+    /// An error if writing failed, or `ErrorKind::InvalidInput` if the offer
+    /// holds more than 255 versions or ranges.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_count(writer, self.versions.len())?;
+        for version in &self.versions {
+            write_version(writer, version)?;
+        }
+        write_count(writer, self.ranges.len())?;
+        for range in &self.ranges {
+            let range = range.to_string();
+            write_count(writer, range.len())?;
+            writer.write_all(range.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Reads an offer, see the [wire format](self).
+    ///
+    /// # Returns
+    /// The `Offer`, or an error if reading failed or the data is invalid.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut offer = Offer::new();
+        for _ in 0..read_u8(reader)? {
+            offer.versions.push(read_version(reader)?);
+        }
+        for _ in 0..read_u8(reader)? {
+            let mut range = vec![0; read_u8(reader)?.into()];
+            reader.read_exact(&mut range)?;
+            let range = std::str::from_utf8(&range)
+                .map_err(|_| invalid_data(VersionError::InvalidRequirement))?;
+            offer
+                .ranges
+                .push(VersionReq::parse(range).map_err(invalid_data)?);
+        }
+        Ok(offer)
+    }
+}
+
+/// Picks the highest version supported by both offers.
+///
+/// The result does not depend on which offer is passed as `server` and which as
+/// `client`, apart from how a rejection is reported.
+///
+/// # Parameters
+/// - `server`: The server's offer.
+/// - `client`: The client's offer.
+///
+/// # Returns
+/// The agreed version, or a `Rejection` explaining why there is none.
+pub fn negotiate(server: &Offer, client: &Offer) -> std::result::Result<Version, Rejection> {
+    if client.is_empty() {
+        return Err(Rejection::ClientOfferEmpty);
+    }
+    if server.is_empty() {
+        return Err(Rejection::ServerOfferEmpty);
+    }
+
+    server
+        .versions
+        .iter()
+        .chain(&client.versions)
+        .filter(|candidate| server.supports(candidate) && client.supports(candidate))
+        .max()
+        .copied()
+        .or_else(|| {
+            let common = server.version_set().intersection(&client.version_set());
+            pick_from(&common, |candidate| {
+                server.supports(candidate) && client.supports(candidate)
+            })
+        })
+        .ok_or(Rejection::NoCommonVersion {
+            server_highest: server.versions.iter().max().copied(),
+        })
+}
+
+impl Answer {
+    /// Negotiates a version and wraps the outcome as an answer.
+    ///
+    /// # Parameters
+    /// - `server`: The server's offer.
+    /// - `client`: The client's offer.
+    pub fn negotiate(server: &Offer, client: &Offer) -> Self {
+        match negotiate(server, client) {
+            Ok(version) => Answer::Accepted(version),
+            Err(rejection) => Answer::Rejected(rejection),
+        }
+    }
+
+    /// Writes the answer, see the [wire format](self).
+    ///
+    /// # Returns
+    /// An error if writing failed.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        match self {
+            Answer::Accepted(version) => {
+                writer.write_all(&[0])?;
+                write_version(writer, version)
+            }
+            Answer::Rejected(Rejection::ClientOfferEmpty) => writer.write_all(&[1]),
+            Answer::Rejected(Rejection::ServerOfferEmpty) => writer.write_all(&[2]),
+            Answer::Rejected(Rejection::NoCommonVersion { server_highest }) => {
+                writer.write_all(&[3])?;
+                write_optional_version(writer, server_highest.as_ref())
+            }
+        }
+    }
+
+    /// Reads an answer, see the [wire format](self).
+    ///
+    /// # Returns
+    /// The `Answer`, or an error if reading failed or the data is invalid.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(match read_u8(reader)? {
+            0 => Answer::Accepted(read_version(reader)?),
+            1 => Answer::Rejected(Rejection::ClientOfferEmpty),
+            2 => Answer::Rejected(Rejection::ServerOfferEmpty),
+            3 => Answer::Rejected(Rejection::NoCommonVersion {
+                server_highest: read_optional_version(reader)?,
+            }),
+            tag => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unknown answer tag {tag}"),
+                ))
+            }
+        })
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::ClientOfferEmpty => write!(f, "The client did not offer any version"),
+            Rejection::ServerOfferEmpty => write!(f, "The server did not offer any version"),
+            Rejection::NoCommonVersion { server_highest } => {
+                write!(f, "No version is supported by both client and server")?;
+                if let Some(server) = server_highest {
+                    write!(f, " (server supports up to {server})")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+/// Picks a version from the highest interval of a set that has one: its inclusive
+/// upper bound, or otherwise the lowest release it contains.
+fn pick_from(set: &VersionSet, supported: impl Fn(&Version) -> bool) -> Option<Version> {
+    set.intervals().iter().rev().find_map(|(lower, upper)| {
+        let lowest = match lower {
+            Included(version) if version.is_prerelease() => {
+                Some(version.with_prerelease(Prerelease::EMPTY))
+            }
+            Included(version) => Some(*version),
+            Excluded(version) if version.is_prerelease() => {
+                Some(version.with_prerelease(Prerelease::EMPTY))
+            }
+            Excluded(version) => version.bumped(BumpLevel::Patch).ok(),
+            Unbounded => Some(Version::new(0, 0, 0)),
+        };
+        let highest = match upper {
+            Included(version) => Some(*version),
+            _ => None,
+        };
+        highest
+            .into_iter()
+            .chain(lowest)
+            .find(|candidate| set.contains(candidate) && supported(candidate))
+    })
+}
+
+fn invalid_data(err: VersionError) -> Error {
+    Error::new(ErrorKind::InvalidData, err)
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> Result<u8> {
+    let mut byte = [0];
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+fn write_count<W: Write>(writer: &mut W, count: usize) -> Result<()> {
+    let count = u8::try_from(count)
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, "more than 255 entries"))?;
+    writer.write_all(&[count])
+}
+
+fn write_version<W: Write>(writer: &mut W, version: &Version) -> Result<()> {
+    let mut buf = [0; Version::MAX_VARINT_SIZE];
+    let len = version.encode_varint(&mut buf).map_err(invalid_data)?;
+    write_count(writer, len)?;
+    writer.write_all(&buf[..len])
+}
+
+fn read_version<R: Read>(reader: &mut R) -> Result<Version> {
+    let mut buf = [0; Version::MAX_VARINT_SIZE];
+    let len = usize::from(read_u8(reader)?);
+    let buf = buf
+        .get_mut(..len)
+        .ok_or_else(|| invalid_data(VersionError::BufferTooSmall))?;
+    reader.read_exact(buf)?;
+    let (version, consumed) = Version::decode_varint(buf).map_err(invalid_data)?;
+    if consumed != len {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "trailing bytes after version",
+        ));
+    }
+    Ok(version)
+}
+
+fn write_optional_version<W: Write>(writer: &mut W, version: Option<&Version>) -> Result<()> {
+    match version {
+        Some(version) => {
+            writer.write_all(&[1])?;
+            write_version(writer, version)
+        }
+        None => writer.write_all(&[0]),
+    }
+}
+
+fn read_optional_version<R: Read>(reader: &mut R) -> Result<Option<Version>> {
+    match read_u8(reader)? {
+        0 => Ok(None),
+        1 => read_version(reader).map(Some),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            "invalid optional version marker",
+        )),
+    }
+}
@@ -0,0 +1,466 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Version ranges in npm / node-semver syntax.
+//!
+//! A range is a set of comparator sets separated by `||`. A version matches the
+//! range if it matches every comparator in at least one of the sets.
+//!
+//! While parsing, the shorthand forms are desugared into primitive comparators
+//! the same way node-semver does it:
+//!
+//! | Range            | Desugared                       |
+//! |------------------|---------------------------------|
+//! | `*`, `x`, `""`   | `*` (any version)               |
+//! | `1.x`, `1`       | `>=1.0.0 <2.0.0-0`              |
+//! | `1.2.x`, `1.2`   | `>=1.2.0 <1.3.0-0`              |
+//! | `~1.2.3`         | `>=1.2.3 <1.3.0-0`              |
+//! | `~1.2`           | `>=1.2.0 <1.3.0-0`              |
+//! | `~1`             | `>=1.0.0 <2.0.0-0`              |
+//! | `^1.2.3`         | `>=1.2.3 <2.0.0-0`              |
+//! | `^0.2.3`         | `>=0.2.3 <0.3.0-0`              |
+//! | `^0.0.3`         | `>=0.0.3 <0.0.4-0`              |
+//! | `^0.0.x`         | `>=0.0.0 <0.1.0-0`              |
+//! | `1.2 - 2.3.4`    | `>=1.2.0 <=2.3.4`               |
+//! | `1.2.3 - 2.3`    | `>=1.2.3 <2.4.0-0`              |
+//! | `>1.2`           | `>=1.3.0`                       |
+//! | `<=1.2`          | `<1.3.0-0`                      |
+//!
+//! As in node-semver, a pre-release version only matches a comparator set if one
+//! of its comparators names the same `major.minor.patch` with a pre-release.
+//! When the next minor or patch would overflow `u16`, an upper bound moves to the
+//! next higher component, and it is only left out when the major would overflow.
+//!
+//! # Examples
+//!
+//! ```
+//! use app_version::npm::Range;
+//! use app_version::Version;
+//!
+//! let range: Range = "1.2.x || >=2.0.0 <2.3.0".parse().unwrap();
+//! assert!(range.matches(&Version::new(1, 2, 7)));
+//! assert!(range.matches(&Version::new(2, 2, 0)));
+//! assert!(!range.matches(&Version::new(2, 3, 0)));
+//! assert_eq!(range.to_string(), ">=1.2.0 <1.3.0-0 || >=2.0.0 <2.3.0");
+//! ```
+use crate::{BuildMetadata, Prerelease, Version, VersionError};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// A node-semver range, e.g. `1.2.x || >=2.0.0 <2.3.0`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Range {
+    sets: Vec<Vec<Comparator>>,
+}
+
+/// A primitive comparator, e.g. `>=1.2.0`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Comparator {
+    op: Operator,
+    version: Version,
+}
+
+/// The operator of a primitive [`Comparator`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Operator {
+    /// `1.2.3`
+    Eq,
+    /// `<1.2.3`
+    Less,
+    /// `<=1.2.3`
+    LessEq,
+    /// `>1.2.3`
+    Greater,
+    /// `>=1.2.3`
+    GreaterEq,
+}
+
+impl Range {
+    /// Parses a range in node-semver syntax.
+    ///
+    /// # Parameters
+    /// - `s`: The range, e.g. `"^1.2.3"` or `"1.2 - 1.4"`.
+    ///
+    /// # Returns
+    /// The desugared `Range`, or `VersionError::InvalidRange` if `s` is not valid.
+    pub fn parse(s: &str) -> Result<Self, VersionError> {
+        let sets = s
+            .split("||")
+            .map(parse_set)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { sets })
+    }
+
+    /// Returns the desugared comparator sets, one of which must match.
+    ///
+    /// An empty comparator set matches any version.
+    pub fn sets(&self) -> &[Vec<Comparator>] {
+        &self.sets
+    }
+
+    /// Checks if a version satisfies this range.
+    ///
+    /// # Parameters
+    /// - `version`: The version to check.
+    ///
+    /// # Returns
+    /// `true` if every comparator of at least one set matches `version`, otherwise `false`.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.sets.iter().any(|set| set_matches(set, version))
+    }
+}
+
+impl Comparator {
+    /// Creates a primitive comparator.
+    pub const fn new(op: Operator, version: Version) -> Self {
+        Self { op, version }
+    }
+
+    /// Returns the operator.
+    pub const fn op(&self) -> Operator {
+        self.op
+    }
+
+    /// Returns the version compared against.
+    pub const fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Checks if a version satisfies this comparator, ignoring the pre-release rule.
+    pub fn matches(&self, version: &Version) -> bool {
+        let ordering = version.cmp_precedence(&self.version);
+        match self.op {
+            Operator::Eq => ordering == Ordering::Equal,
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessEq => ordering != Ordering::Greater,
+            Operator::Greater => ordering == Ordering::Greater,
+            Operator::GreaterEq => ordering != Ordering::Less,
+        }
+    }
+}
+
+fn set_matches(set: &[Comparator], version: &Version) -> bool {
+    if !set.iter().all(|comparator| comparator.matches(version)) {
+        return false;
+    }
+    if !version.is_prerelease() {
+        return true;
+    }
+    set.iter().any(|comparator| {
+        let bound = comparator.version;
+        bound.is_prerelease()
+            && bound.major() == version.major()
+            && bound.minor() == version.minor()
+            && bound.patch() == version.patch()
+    })
+}
+
+/// A possibly incomplete version, where `None` stands for an omitted or `x` component.
+#[derive(Clone, Copy)]
+struct Partial {
+    major: Option<u16>,
+    minor: Option<u16>,
+    patch: Option<u16>,
+    pre: Prerelease,
+}
+
+impl Partial {
+    fn parse(s: &str) -> Result<Self, VersionError> {
+        let s = s.strip_prefix('=').unwrap_or(s);
+        let s = s.strip_prefix('v').unwrap_or(s);
+        let s = match s.split_once('+') {
+            Some((rest, build)) => {
+                BuildMetadata::new(build).map_err(|_| VersionError::InvalidRange)?;
+                rest
+            }
+            None => s,
+        };
+        let (s, pre) = match s.split_once('-') {
+            Some((rest, pre)) => (
+                rest,
+                Prerelease::new(pre)
+                    .ok()
+                    .filter(|pre| !pre.is_empty())
+                    .ok_or(VersionError::InvalidRange)?,
+            ),
+            None => (s, Prerelease::EMPTY),
+        };
+
+        let mut components = [None; 3];
+        if !s.is_empty() {
+            let mut parts = s.split('.');
+            let mut wildcard = false;
+            for component in &mut components {
+                let Some(part) = parts.next() else {
+                    break;
+                };
+                match part {
+                    "x" | "X" | "*" => wildcard = true,
+                    _ if !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()) => {
+                        let value = part.parse().map_err(|_| VersionError::InvalidRange)?;
+                        if !wildcard {
+                            *component = Some(value);
+                        }
+                    }
+                    _ => return Err(VersionError::InvalidRange),
+                }
+            }
+            if parts.next().is_some() {
+                return Err(VersionError::InvalidRange);
+            }
+        }
+
+        let [major, minor, patch] = components;
+        // A pre-release only applies to a complete version.
+        if patch.is_none() && !pre.is_empty() {
+            return Err(VersionError::InvalidRange);
+        }
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+}
+
+fn version(major: u16, minor: u16, patch: u16) -> Version {
+    Version::new(major, minor, patch)
+}
+
+/// The exclusive upper bound `<major.minor.patch-0`, which also excludes pre-releases.
+fn below(major: u16, minor: u16, patch: u16) -> Comparator {
+    let lowest = Prerelease::new("0").unwrap_or_default();
+    Comparator::new(
+        Operator::Less,
+        version(major, minor, patch).with_prerelease(lowest),
+    )
+}
+
+fn below_next_major(major: u16) -> Option<Comparator> {
+    major.checked_add(1).map(|major| below(major, 0, 0))
+}
+
+fn below_next_minor(major: u16, minor: u16) -> Option<Comparator> {
+    match minor.checked_add(1) {
+        Some(minor) => Some(below(major, minor, 0)),
+        None => below_next_major(major),
+    }
+}
+
+fn below_next_patch(major: u16, minor: u16, patch: u16) -> Option<Comparator> {
+    match patch.checked_add(1) {
+        Some(patch) => Some(below(major, minor, patch)),
+        None => below_next_minor(major, minor),
+    }
+}
+
+fn at_least(version: Version) -> Comparator {
+    Comparator::new(Operator::GreaterEq, version)
+}
+
+fn nothing() -> Comparator {
+    below(0, 0, 0)
+}
+
+fn parse_set(s: &str) -> Result<Vec<Comparator>, VersionError> {
+    let tokens: Vec<&str> = s.split_whitespace().collect();
+    if let [from, "-", to] = tokens[..] {
+        return Ok(hyphen(Partial::parse(from)?, Partial::parse(to)?));
+    }
+
+    let mut comparators = Vec::new();
+    // node-semver allows whitespace between an operator and its version, e.g. `>= 1.2.3`.
+    let mut pending_op = String::new();
+    for token in tokens {
+        if matches!(token, "<" | "<=" | ">" | ">=" | "=" | "~" | "~>" | "^") {
+            if !pending_op.is_empty() {
+                return Err(VersionError::InvalidRange);
+            }
+            pending_op.push_str(token);
+            continue;
+        }
+        let token = std::mem::take(&mut pending_op) + token;
+        desugar(&token, &mut comparators)?;
+    }
+    if !pending_op.is_empty() {
+        return Err(VersionError::InvalidRange);
+    }
+    Ok(comparators)
+}
+
+fn desugar(token: &str, out: &mut Vec<Comparator>) -> Result<(), VersionError> {
+    if let Some(rest) = token.strip_prefix("~>").or_else(|| token.strip_prefix('~')) {
+        tilde(Partial::parse(rest)?, out);
+        return Ok(());
+    }
+    if let Some(rest) = token.strip_prefix('^') {
+        caret(Partial::parse(rest)?, out);
+        return Ok(());
+    }
+
+    let (op, rest) = [
+        (">=", Operator::GreaterEq),
+        ("<=", Operator::LessEq),
+        (">", Operator::Greater),
+        ("<", Operator::Less),
+    ]
+    .iter()
+    .find_map(|(prefix, op)| token.strip_prefix(prefix).map(|rest| (*op, rest)))
+    .unwrap_or((Operator::Eq, token));
+    x_range(op, Partial::parse(rest)?, out);
+    Ok(())
+}
+
+fn tilde(partial: Partial, out: &mut Vec<Comparator>) {
+    let Some(major) = partial.major else {
+        return;
+    };
+    let Some(minor) = partial.minor else {
+        out.push(at_least(version(major, 0, 0)));
+        out.extend(below_next_major(major));
+        return;
+    };
+    let patch = partial.patch.unwrap_or(0);
+    out.push(at_least(
+        version(major, minor, patch).with_prerelease(partial.pre),
+    ));
+    out.extend(below_next_minor(major, minor));
+}
+
+fn caret(partial: Partial, out: &mut Vec<Comparator>) {
+    let Some(major) = partial.major else {
+        return;
+    };
+    let Some(minor) = partial.minor else {
+        out.push(at_least(version(major, 0, 0)));
+        out.extend(below_next_major(major));
+        return;
+    };
+    let Some(patch) = partial.patch else {
+        out.push(at_least(version(major, minor, 0)));
+        out.extend(if major == 0 {
+            below_next_minor(major, minor)
+        } else {
+            below_next_major(major)
+        });
+        return;
+    };
+    out.push(at_least(
+        version(major, minor, patch).with_prerelease(partial.pre),
+    ));
+    out.extend(match (major, minor) {
+        (0, 0) => below_next_patch(major, minor, patch),
+        (0, _) => below_next_minor(major, minor),
+        _ => below_next_major(major),
+    });
+}
+
+fn x_range(op: Operator, partial: Partial, out: &mut Vec<Comparator>) {
+    let Some(major) = partial.major else {
+        if matches!(op, Operator::Less | Operator::Greater) {
+            out.push(nothing());
+        }
+        return;
+    };
+
+    if let (Some(minor), Some(patch)) = (partial.minor, partial.patch) {
+        let exact = version(major, minor, patch).with_prerelease(partial.pre);
+        out.push(Comparator::new(op, exact));
+        return;
+    }
+
+    let minor = partial.minor;
+
+    match op {
+        Operator::Eq => {
+            out.push(at_least(version(major, minor.unwrap_or(0), 0)));
+            out.extend(match minor {
+                None => below_next_major(major),
+                Some(minor) => below_next_minor(major, minor),
+            });
+        }
+        Operator::GreaterEq => out.push(at_least(version(major, minor.unwrap_or(0), 0))),
+        Operator::Greater => {
+            let next = match minor {
+                None => major.checked_add(1).map(|major| version(major, 0, 0)),
+                Some(minor) => match minor.checked_add(1) {
+                    Some(minor) => Some(version(major, minor, 0)),
+                    None => major.checked_add(1).map(|major| version(major, 0, 0)),
+                },
+            };
+            out.push(next.map_or_else(nothing, at_least));
+        }
+        Operator::Less => out.push(below(major, minor.unwrap_or(0), 0)),
+        Operator::LessEq => out.extend(match minor {
+            None => below_next_major(major),
+            Some(minor) => below_next_minor(major, minor),
+        }),
+    }
+}
+
+fn hyphen(from: Partial, to: Partial) -> Vec<Comparator> {
+    let mut comparators = Vec::new();
+    if let Some(major) = from.major {
+        let lower = match (from.minor, from.patch) {
+            (Some(minor), Some(patch)) => version(major, minor, patch).with_prerelease(from.pre),
+            (minor, _) => version(major, minor.unwrap_or(0), 0),
+        };
+        comparators.push(at_least(lower));
+    }
+    if let Some(major) = to.major {
+        match (to.minor, to.patch) {
+            (None, _) => comparators.extend(below_next_major(major)),
+            (Some(minor), None) => comparators.extend(below_next_minor(major, minor)),
+            (Some(minor), Some(patch)) => comparators.push(Comparator::new(
+                Operator::LessEq,
+                version(major, minor, patch).with_prerelease(to.pre),
+            )),
+        }
+    }
+    comparators
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, set) in self.sets.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" || ")?;
+            }
+            if set.is_empty() {
+                f.write_str("*")?;
+            }
+            for (index, comparator) in set.iter().enumerate() {
+                if index > 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{comparator}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self.op {
+            Operator::Eq => "",
+            Operator::Less => "<",
+            Operator::LessEq => "<=",
+            Operator::Greater => ">",
+            Operator::GreaterEq => ">=",
+        };
+        write!(f, "{op}{}", self.version)
+    }
+}
+
+impl FromStr for Range {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
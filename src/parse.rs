@@ -0,0 +1,158 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::{BuildMetadata, Component, NumberErrorKind, Prerelease, Version, VersionError};
+
+impl Version {
+    /// Parses a version from raw bytes, such as a network buffer.
+    ///
+    /// Accepts the same `major.minor.patch[-pre-release][+build]` syntax as `FromStr`,
+    /// which calls this. The input is scanned once and nothing is allocated unless
+    /// parsing fails.
+    ///
+    /// # Parameters
+    /// - `bytes`: The version string as bytes, e.g. `b"1.2.3"`.
+    ///
+    /// # Returns
+    /// A `Version` instance, or a `VersionError` if `bytes` is not a valid version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use app_version::Version;
+    ///
+    /// let version = Version::parse_bytes(b"1.4.2-rc.1").unwrap();
+    /// assert_eq!(version, "1.4.2-rc.1".parse().unwrap());
+    /// assert!(Version::parse_bytes(b"1.4.\xff").is_err());
+    /// ```
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Self, VersionError> {
+        let mut numbers = [Number::at(0); 3];
+        let mut found = 1;
+        let mut core_end = bytes.len();
+        for (index, &byte) in bytes.iter().enumerate() {
+            match byte {
+                b'-' | b'+' => {
+                    core_end = index;
+                    break;
+                }
+                b'.' => {
+                    if found < numbers.len() {
+                        numbers[found] = Number::at(index + 1);
+                    }
+                    found += 1;
+                }
+                _ if found <= numbers.len() => numbers[found - 1].push(byte),
+                _ => {}
+            }
+        }
+
+        let (pre, build) = parse_suffix(&bytes[core_end..])?;
+        if found != numbers.len() {
+            return Err(VersionError::InvalidComponentCount {
+                expected: numbers.len(),
+                found,
+            });
+        }
+
+        let finish =
+            |number: Number, component| number.finish(component, &bytes[number.start..number.end]);
+        let [major, minor, patch] = numbers;
+        Ok(Version::new(
+            finish(major, Component::Major)?,
+            finish(minor, Component::Minor)?,
+            finish(patch, Component::Patch)?,
+        )
+        .with_prerelease(pre)
+        .with_build(build))
+    }
+}
+
+/// A major, minor or patch version, accumulated while scanning.
+#[derive(Clone, Copy)]
+struct Number {
+    start: usize,
+    end: usize,
+    value: u16,
+    error: Option<NumberErrorKind>,
+}
+
+impl Number {
+    const fn at(start: usize) -> Self {
+        Self {
+            start,
+            end: start,
+            value: 0,
+            error: None,
+        }
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.end += 1;
+        if !byte.is_ascii_digit() {
+            self.error = Some(NumberErrorKind::InvalidDigit);
+        } else if self.error.is_none() {
+            match self
+                .value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(u16::from(byte - b'0')))
+            {
+                Some(value) => self.value = value,
+                None => self.error = Some(NumberErrorKind::Overflow),
+            }
+        }
+    }
+
+    /// `text` is the bytes that were pushed, used in the error message.
+    fn finish(self, component: Component, text: &[u8]) -> Result<u16, VersionError> {
+        let kind = if self.start == self.end {
+            Some(NumberErrorKind::Empty)
+        } else {
+            self.error
+        };
+        match kind {
+            None => Ok(self.value),
+            Some(kind) => Err(VersionError::InvalidNumber {
+                component,
+                offset: self.start,
+                text: String::from_utf8_lossy(text).into_owned(),
+                kind,
+            }),
+        }
+    }
+}
+
+/// Parses a numeric version component.
+///
+/// `offset` is only used to report where `text` starts in the parsed string.
+pub(crate) fn parse_number(
+    text: &str,
+    component: Component,
+    offset: usize,
+) -> Result<u16, VersionError> {
+    let mut number = Number::at(offset);
+    for byte in text.bytes() {
+        number.push(byte);
+    }
+    number.finish(component, text.as_bytes())
+}
+
+/// Parses the `-pre-release` and `+build` that may follow the version core.
+pub(crate) fn parse_suffix(suffix: &[u8]) -> Result<(Prerelease, BuildMetadata), VersionError> {
+    let (pre, build) = match suffix.iter().position(|&byte| byte == b'+') {
+        Some(plus) => (&suffix[..plus], Some(&suffix[plus + 1..])),
+        None => (suffix, None),
+    };
+    let build = match build {
+        Some([]) => return Err(VersionError::InvalidBuildMetadata),
+        Some(build) => BuildMetadata::from_bytes(build)?,
+        None => BuildMetadata::EMPTY,
+    };
+    // A non-empty `pre` starts with the '-' that ended the version core.
+    let pre = match pre {
+        [] => Prerelease::EMPTY,
+        [_] => return Err(VersionError::InvalidPrerelease),
+        [_, pre @ ..] => Prerelease::from_bytes(pre)?,
+    };
+    Ok((pre, build))
+}
@@ -0,0 +1,169 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::identifiers::{is_numeric, Identifiers, IdentifiersError};
+use crate::VersionError;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// The pre-release part of a version, e.g. `rc.1` in `1.2.0-rc.1`.
+///
+/// A pre-release is a list of dot-separated identifiers made of ASCII
+/// alphanumerics and hyphens. Numeric identifiers must not have leading zeros.
+/// The identifiers are stored inline, so a pre-release is limited to
+/// [`Prerelease::MAX_LEN`] bytes.
+///
+/// # Examples
+///
+/// ```
+/// use app_version::Prerelease;
+///
+/// let beta: Prerelease = "beta.2".parse().unwrap();
+/// let rc: Prerelease = "rc.1".parse().unwrap();
+/// assert!(beta < rc);
+/// assert_eq!(rc.identifiers().collect::<Vec<_>>(), ["rc", "1"]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Prerelease {
+    identifiers: Identifiers<{ Prerelease::MAX_LEN }>,
+}
+
+impl Prerelease {
+    /// The maximum length, in bytes, of a pre-release.
+    pub const MAX_LEN: usize = 32;
+
+    /// The empty pre-release, used by normal releases.
+    pub const EMPTY: Self = Self {
+        identifiers: Identifiers::EMPTY,
+    };
+
+    /// Creates a pre-release from dot-separated identifiers.
+    ///
+    /// # Parameters
+    /// - `s`: The identifiers, e.g. `"alpha.1"`. An empty string gives [`Prerelease::EMPTY`].
+    ///
+    /// # Returns
+    /// The `Prerelease`, or a `VersionError` if `s` is not a valid pre-release.
+    pub fn new(s: &str) -> Result<Self, VersionError> {
+        Self::from_bytes(s.as_bytes())
+    }
+
+    pub(crate) fn from_bytes(s: &[u8]) -> Result<Self, VersionError> {
+        Self::parse_const(s).map_err(|err| match err {
+            IdentifiersError::Invalid => VersionError::InvalidPrerelease,
+            IdentifiersError::TooLong => VersionError::PrereleaseTooLong,
+        })
+    }
+
+    pub(crate) const fn parse_const(s: &[u8]) -> Result<Self, IdentifiersError> {
+        match Identifiers::parse(s, false) {
+            Ok(identifiers) => Ok(Self { identifiers }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns `true` if there are no identifiers.
+    pub const fn is_empty(&self) -> bool {
+        self.identifiers.is_empty()
+    }
+
+    /// Returns the identifiers as a dot-separated string.
+    pub fn as_str(&self) -> &str {
+        self.identifiers.as_str()
+    }
+
+    /// Returns an iterator over the individual identifiers.
+    pub fn identifiers(&self) -> impl Iterator<Item = &str> {
+        self.as_str()
+            .split('.')
+            .filter(|identifier| !identifier.is_empty())
+    }
+}
+
+impl Default for Prerelease {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+fn cmp_identifier(a: &str, b: &str) -> Ordering {
+    match (is_numeric(a), is_numeric(b)) {
+        // Numeric identifiers have no leading zeros, so the longer one is larger.
+        (true, true) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.cmp(b),
+    }
+}
+
+impl Ord for Prerelease {
+    /// Orders pre-releases by SemVer precedence.
+    ///
+    /// The empty pre-release sorts last, since a release has higher
+    /// precedence than any of its pre-releases.
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Greater,
+            (false, true) => return Ordering::Less,
+            (false, false) => {}
+        }
+
+        let mut lhs = self.identifiers();
+        let mut rhs = other.identifiers();
+        loop {
+            match (lhs.next(), rhs.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(a), Some(b)) => match cmp_identifier(a, b) {
+                    Ordering::Equal => {}
+                    ordering => return ordering,
+                },
+            }
+        }
+    }
+}
+
+impl PartialOrd for Prerelease {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Prerelease {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Prerelease {}
+
+impl Hash for Prerelease {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for Prerelease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Prerelease(\"{}\")", self.as_str())
+    }
+}
+
+impl fmt::Display for Prerelease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Prerelease {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
@@ -0,0 +1,425 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::{BuildMetadata, Prerelease, Version, VersionError};
+use std::fmt;
+use std::str::FromStr;
+
+/// A version requirement in Cargo syntax, e.g. `>=1.2, <2`.
+///
+/// A requirement is a comma-separated list of comparators, all of which must
+/// match. A version without an operator, e.g. `1.3`, is a caret requirement.
+///
+/// A pre-release version only matches if at least one comparator names the same
+/// `major.minor.patch` with a pre-release, so `>=1.2.0` does not match `1.3.0-rc.1`.
+///
+/// # Examples
+///
+/// ```
+/// use app_version::{Version, VersionReq};
+///
+/// let req: VersionReq = ">=1.2, <2".parse().unwrap();
+/// assert!(req.matches(&Version::new(1, 3, 0)));
+/// assert!(!req.matches(&Version::new(2, 0, 0)));
+/// assert_eq!(req.to_string(), ">=1.2, <2");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+/// A single comparator in a [`VersionReq`], e.g. `^1.3`.
+///
+/// Minor and patch may be left out, e.g. `~1`, or given as wildcards, e.g. `1.*`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Comparator {
+    op: Op,
+    major: u16,
+    minor: Option<u16>,
+    patch: Option<u16>,
+    pre: Prerelease,
+}
+
+/// The operator of a [`Comparator`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Op {
+    /// `=1.2.3`: exactly the given version, or any version in the given range if
+    /// minor or patch are left out.
+    Exact,
+    /// `>1.2.3`
+    Greater,
+    /// `>=1.2.3`
+    GreaterEq,
+    /// `<1.2.3`
+    Less,
+    /// `<=1.2.3`
+    LessEq,
+    /// `~1.2.3`: patch updates only, or minor updates if minor is left out.
+    Tilde,
+    /// `^1.2.3`: updates that do not change the leftmost non-zero component.
+    Caret,
+    /// `1.*` or `1.2.*`: any version matching the given components.
+    Wildcard,
+}
+
+impl VersionReq {
+    /// The requirement `*`, which matches any version that is not a pre-release.
+    pub const STAR: Self = Self {
+        comparators: Vec::new(),
+    };
+
+    /// Parses a requirement in Cargo syntax.
+    ///
+    /// A `*` comparator matches any version, so it adds nothing to the other comparators.
+    ///
+    /// # Parameters
+    /// - `s`: The requirement, e.g. `"^1.3"`, `">=1.2, <2"` or `"*"`.
+    ///
+    /// # Returns
+    /// The `VersionReq`, or `VersionError::InvalidRequirement` if `s` is not valid.
+    pub fn parse(s: &str) -> Result<Self, VersionError> {
+        let comparators = s
+            .split(',')
+            .filter(|part| part.trim() != "*")
+            .map(Comparator::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { comparators })
+    }
+
+    /// Returns the comparators, all of which must match.
+    pub fn comparators(&self) -> &[Comparator] {
+        &self.comparators
+    }
+
+    /// Checks if a version satisfies this requirement.
+    ///
+    /// # Parameters
+    /// - `version`: The version to check.
+    ///
+    /// # Returns
+    /// `true` if every comparator matches `version`, otherwise `false`.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators
+            .iter()
+            .all(|comparator| comparator.matches(version))
+            && self.allows_prerelease_of(version)
+    }
+
+    fn allows_prerelease_of(&self, version: &Version) -> bool {
+        if !version.is_prerelease() {
+            return true;
+        }
+        self.comparators.iter().any(|comparator| {
+            comparator.major == version.major()
+                && comparator.minor == Some(version.minor())
+                && comparator.patch == Some(version.patch())
+                && !comparator.pre.is_empty()
+        })
+    }
+}
+
+impl Default for VersionReq {
+    fn default() -> Self {
+        Self::STAR
+    }
+}
+
+impl Comparator {
+    /// Parses a single comparator, e.g. `">=1.2"`.
+    ///
+    /// # Parameters
+    /// - `s`: The comparator.
+    ///
+    /// # Returns
+    /// The `Comparator`, or `VersionError::InvalidRequirement` if `s` is not valid.
+    pub fn parse(s: &str) -> Result<Self, VersionError> {
+        let s = s.trim();
+        let (op, rest) = [
+            (">=", Op::GreaterEq),
+            ("<=", Op::LessEq),
+            (">", Op::Greater),
+            ("<", Op::Less),
+            ("=", Op::Exact),
+            ("~", Op::Tilde),
+            ("^", Op::Caret),
+        ]
+        .iter()
+        .find_map(|(prefix, op)| s.strip_prefix(prefix).map(|rest| (Some(*op), rest)))
+        .unwrap_or((None, s));
+        let rest = rest.trim_start();
+
+        // Cargo accepts build metadata in a requirement, but ignores it when matching.
+        let (rest, has_build) = match rest.split_once('+') {
+            Some((_, "")) => return Err(VersionError::InvalidRequirement),
+            Some((core, build)) => {
+                BuildMetadata::new(build).map_err(|_| VersionError::InvalidRequirement)?;
+                (core, true)
+            }
+            None => (rest, false),
+        };
+
+        let (rest, pre) = match rest.split_once('-') {
+            Some((_, "")) => return Err(VersionError::InvalidRequirement),
+            Some((core, pre)) => (
+                core,
+                Prerelease::new(pre).map_err(|_| VersionError::InvalidRequirement)?,
+            ),
+            None => (rest, Prerelease::EMPTY),
+        };
+
+        let mut parts = rest.split('.');
+        let major = parse_component(parts.next())?
+            .flatten()
+            .ok_or(VersionError::InvalidRequirement)?;
+        let minor = parse_component(parts.next())?;
+        let patch = parse_component(parts.next())?;
+        if parts.next().is_some() {
+            return Err(VersionError::InvalidRequirement);
+        }
+
+        let has_wildcard = matches!(minor, Some(None)) || matches!(patch, Some(None));
+        if matches!((minor, patch), (Some(None), Some(Some(_)))) {
+            return Err(VersionError::InvalidRequirement);
+        }
+        let minor = minor.flatten();
+        let patch = patch.flatten();
+        if (!pre.is_empty() || has_build) && patch.is_none() {
+            return Err(VersionError::InvalidRequirement);
+        }
+
+        let op = match op {
+            Some(op) => op,
+            None if has_wildcard => Op::Wildcard,
+            None => Op::Caret,
+        };
+
+        Ok(Self {
+            op,
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    /// Returns the operator.
+    pub const fn op(&self) -> Op {
+        self.op
+    }
+
+    /// Returns the major version number.
+    pub const fn major(&self) -> u16 {
+        self.major
+    }
+
+    /// Returns the minor version number, or `None` if it was left out.
+    pub const fn minor(&self) -> Option<u16> {
+        self.minor
+    }
+
+    /// Returns the patch version number, or `None` if it was left out.
+    pub const fn patch(&self) -> Option<u16> {
+        self.patch
+    }
+
+    /// Returns the pre-release, which is empty if none was given.
+    pub const fn prerelease(&self) -> &Prerelease {
+        &self.pre
+    }
+
+    /// Checks if a version satisfies this comparator.
+    ///
+    /// Unlike [`VersionReq::matches`], this does not apply the pre-release rule.
+    ///
+    /// # Parameters
+    /// - `version`: The version to check.
+    ///
+    /// # Returns
+    /// `true` if `version` matches, otherwise `false`.
+    pub fn matches(&self, version: &Version) -> bool {
+        match self.op {
+            Op::Exact | Op::Wildcard => self.matches_exact(version),
+            Op::Greater => self.matches_greater(version),
+            Op::GreaterEq => self.matches_exact(version) || self.matches_greater(version),
+            Op::Less => self.matches_less(version),
+            Op::LessEq => self.matches_exact(version) || self.matches_less(version),
+            Op::Tilde => self.matches_tilde(version),
+            Op::Caret => self.matches_caret(version),
+        }
+    }
+
+    fn matches_exact(&self, version: &Version) -> bool {
+        version.major() == self.major
+            && self.minor.is_none_or(|minor| version.minor() == minor)
+            && self.patch.is_none_or(|patch| version.patch() == patch)
+            && (self.patch.is_none() || *version.prerelease() == self.pre)
+    }
+
+    fn matches_greater(&self, version: &Version) -> bool {
+        if version.major() != self.major {
+            return version.major() > self.major;
+        }
+        let Some(minor) = self.minor else {
+            return false;
+        };
+        if version.minor() != minor {
+            return version.minor() > minor;
+        }
+        let Some(patch) = self.patch else {
+            return false;
+        };
+        if version.patch() != patch {
+            return version.patch() > patch;
+        }
+        *version.prerelease() > self.pre
+    }
+
+    fn matches_less(&self, version: &Version) -> bool {
+        if version.major() != self.major {
+            return version.major() < self.major;
+        }
+        let Some(minor) = self.minor else {
+            return false;
+        };
+        if version.minor() != minor {
+            return version.minor() < minor;
+        }
+        let Some(patch) = self.patch else {
+            return false;
+        };
+        if version.patch() != patch {
+            return version.patch() < patch;
+        }
+        *version.prerelease() < self.pre
+    }
+
+    fn matches_tilde(&self, version: &Version) -> bool {
+        if version.major() != self.major {
+            return false;
+        }
+        if self.minor.is_some_and(|minor| version.minor() != minor) {
+            return false;
+        }
+        match self.patch {
+            Some(patch) if version.patch() != patch => version.patch() > patch,
+            Some(_) => *version.prerelease() >= self.pre,
+            None => true,
+        }
+    }
+
+    fn matches_caret(&self, version: &Version) -> bool {
+        if version.major() != self.major {
+            return false;
+        }
+        let Some(minor) = self.minor else {
+            return true;
+        };
+        let Some(patch) = self.patch else {
+            return if self.major > 0 {
+                version.minor() >= minor
+            } else {
+                version.minor() == minor
+            };
+        };
+
+        if self.major > 0 {
+            if version.minor() != minor {
+                return version.minor() > minor;
+            }
+            if version.patch() != patch {
+                return version.patch() > patch;
+            }
+        } else if minor > 0 {
+            if version.minor() != minor {
+                return false;
+            }
+            if version.patch() != patch {
+                return version.patch() > patch;
+            }
+        } else if version.minor() != minor || version.patch() != patch {
+            return false;
+        }
+
+        *version.prerelease() >= self.pre
+    }
+}
+
+/// Parses a numeric or wildcard component.
+///
+/// Returns `None` if the component is absent and `Some(None)` for a wildcard.
+fn parse_component(part: Option<&str>) -> Result<Option<Option<u16>>, VersionError> {
+    match part {
+        None => Ok(None),
+        Some("*" | "x" | "X") => Ok(Some(None)),
+        Some(part) if part.len() > 1 && part.starts_with('0') => {
+            Err(VersionError::InvalidRequirement)
+        }
+        Some(part) if !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()) => part
+            .parse()
+            .map(|value| Some(Some(value)))
+            .map_err(|_| VersionError::InvalidRequirement),
+        Some(_) => Err(VersionError::InvalidRequirement),
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.comparators.is_empty() {
+            return f.write_str("*");
+        }
+        for (index, comparator) in self.comparators.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{comparator}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self.op {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Tilde => "~",
+            Op::Caret => "^",
+            Op::Wildcard => "",
+        };
+        write!(f, "{op}{}", self.major)?;
+        match self.minor {
+            Some(minor) => write!(f, ".{minor}")?,
+            None if self.op == Op::Wildcard => return f.write_str(".*"),
+            None => return Ok(()),
+        }
+        match self.patch {
+            Some(patch) => write!(f, ".{patch}")?,
+            None if self.op == Op::Wildcard => return f.write_str(".*"),
+            None => return Ok(()),
+        }
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl FromStr for Comparator {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
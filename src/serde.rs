@@ -0,0 +1,126 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Serde support, enabled with the `serde` feature.
+//!
+//! Human-readable formats such as JSON, TOML and RON use the version string,
+//! e.g. `"1.2.3-rc.1"`. Binary formats use the tuple
+//! `(major, minor, patch, pre_release, build_metadata)`, where the last two are
+//! empty strings when absent.
+//!
+//! [`Prerelease`] and [`BuildMetadata`] are always serialized as strings.
+use crate::{BuildMetadata, Prerelease, Version, VersionError};
+use ::serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+const TUPLE_LEN: usize = 5;
+
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        let mut tuple = serializer.serialize_tuple(TUPLE_LEN)?;
+        tuple.serialize_element(&self.major)?;
+        tuple.serialize_element(&self.minor)?;
+        tuple.serialize_element(&self.patch)?;
+        tuple.serialize_element(&self.pre)?;
+        tuple.serialize_element(&self.build)?;
+        tuple.end()
+    }
+}
+
+impl Serialize for Prerelease {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl Serialize for BuildMetadata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Deserializes a string through `FromStr`, reporting the `VersionError` message on failure.
+struct FromStrVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> FromStrVisitor<T> {
+    const fn new(expecting: &'static str) -> Self {
+        Self {
+            expecting,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: FromStr<Err = VersionError>> Visitor<'_> for FromStrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+struct VersionTupleVisitor;
+
+impl<'de> Visitor<'de> for VersionTupleVisitor {
+    type Value = Version;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a (major, minor, patch, pre-release, build metadata) tuple")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Version, A::Error> {
+        let major = next_element(&mut seq, 0)?;
+        let minor = next_element(&mut seq, 1)?;
+        let patch = next_element(&mut seq, 2)?;
+        let pre = next_element(&mut seq, 3)?;
+        let build = next_element(&mut seq, 4)?;
+        Ok(Version::new(major, minor, patch)
+            .with_prerelease(pre)
+            .with_build(build))
+    }
+}
+
+fn next_element<'de, A: SeqAccess<'de>, T: Deserialize<'de>>(
+    seq: &mut A,
+    index: usize,
+) -> Result<T, A::Error> {
+    seq.next_element()?
+        .ok_or_else(|| de::Error::invalid_length(index, &VersionTupleVisitor))
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FromStrVisitor::new("a version such as \"1.2.3\""))
+        } else {
+            deserializer.deserialize_tuple(TUPLE_LEN, VersionTupleVisitor)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Prerelease {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::new("pre-release identifiers"))
+    }
+}
+
+impl<'de> Deserialize<'de> for BuildMetadata {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::new("build metadata identifiers"))
+    }
+}
@@ -0,0 +1,409 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::npm::{self, Operator};
+use crate::{BuildMetadata, Comparator, Op, Prerelease, Version, VersionError, VersionReq};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+use std::str::FromStr;
+
+/// An interval of versions, as lower and upper bound.
+type Interval = (Bound<Version>, Bound<Version>);
+
+/// A set of versions, stored as a minimal list of disjoint intervals.
+///
+/// Versions are compared by SemVer precedence, so build metadata is ignored. Unlike
+/// [`VersionReq::matches`], a set has no special rule for pre-releases: a pre-release
+/// is in the set if it is within one of the intervals.
+///
+/// The set is displayed as requirements separated by `||`, which is also what
+/// `FromStr` accepts.
+///
+/// # Examples
+///
+/// ```
+/// use app_version::{Version, VersionSet};
+///
+/// let node_a: VersionSet = ">=1.2.0, <2.0.0".parse().unwrap();
+/// let node_b: VersionSet = ">=1.4.0, <1.6.0 || >=1.8.0".parse().unwrap();
+/// let fleet = node_a.intersection(&node_b);
+///
+/// assert!(fleet.contains(&Version::new(1, 5, 0)));
+/// assert!(!fleet.contains(&Version::new(1, 7, 0)));
+/// assert_eq!(fleet.to_string(), ">=1.4.0, <1.6.0 || >=1.8.0, <2.0.0");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct VersionSet {
+    intervals: Vec<Interval>,
+}
+
+impl VersionSet {
+    /// Creates a set that contains no versions.
+    pub const fn empty() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Creates a set that contains every version.
+    pub fn full() -> Self {
+        Self::interval(Unbounded, Unbounded)
+    }
+
+    /// Creates a set that contains only the given version.
+    pub fn exact(version: Version) -> Self {
+        Self::interval(Included(version), Included(version))
+    }
+
+    /// Creates a set from a single interval.
+    ///
+    /// # Parameters
+    /// - `lower`: The lower bound.
+    /// - `upper`: The upper bound.
+    ///
+    /// # Returns
+    /// The `VersionSet`, which is empty if `lower` is above `upper`.
+    pub fn interval(lower: Bound<Version>, upper: Bound<Version>) -> Self {
+        Self::normalized(vec![(lower, upper)])
+    }
+
+    /// Returns the disjoint intervals of the set, in ascending order.
+    pub fn intervals(&self) -> &[(Bound<Version>, Bound<Version>)] {
+        &self.intervals
+    }
+
+    /// Returns `true` if the set contains no versions.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Checks if a version is in the set.
+    ///
+    /// # Parameters
+    /// - `version`: The version to check. Its build metadata is ignored.
+    ///
+    /// # Returns
+    /// `true` if `version` is in the set, otherwise `false`.
+    pub fn contains(&self, version: &Version) -> bool {
+        let version = without_build(*version);
+        self.intervals.iter().any(|(lower, upper)| {
+            let above = match lower {
+                Included(bound) => version >= *bound,
+                Excluded(bound) => version > *bound,
+                Unbounded => true,
+            };
+            let below = match upper {
+                Included(bound) => version <= *bound,
+                Excluded(bound) => version < *bound,
+                Unbounded => true,
+            };
+            above && below
+        })
+    }
+
+    /// Returns the versions that are in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(
+            self.intervals
+                .iter()
+                .chain(&other.intervals)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Returns the versions that are in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        for (lower, upper) in &self.intervals {
+            for (other_lower, other_upper) in &other.intervals {
+                let lower = if cmp_lower(lower, other_lower) == Ordering::Less {
+                    other_lower
+                } else {
+                    lower
+                };
+                let upper = if cmp_upper(upper, other_upper) == Ordering::Greater {
+                    other_upper
+                } else {
+                    upper
+                };
+                intervals.push((*lower, *upper));
+            }
+        }
+        Self::normalized(intervals)
+    }
+
+    /// Returns the versions that are in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement())
+    }
+
+    /// Returns the versions that are not in this set.
+    pub fn complement(&self) -> Self {
+        let mut intervals = Vec::new();
+        let mut lower = Some(Unbounded);
+        for (interval_lower, interval_upper) in &self.intervals {
+            if let (Some(lower), Some(upper)) = (lower, flip(interval_lower)) {
+                intervals.push((lower, upper));
+            }
+            lower = flip(interval_upper);
+        }
+        if let Some(lower) = lower {
+            intervals.push((lower, Unbounded));
+        }
+        Self::normalized(intervals)
+    }
+
+    /// Removes empty intervals, then sorts and merges the rest.
+    fn normalized(intervals: Vec<Interval>) -> Self {
+        let mut intervals: Vec<Interval> = intervals
+            .into_iter()
+            .map(|(lower, upper)| (lower.map(without_build), upper.map(without_build)))
+            .filter(|(lower, upper)| !is_empty_interval(lower, upper))
+            .collect();
+        intervals.sort_by(|(a, _), (b, _)| cmp_lower(a, b));
+
+        let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for (lower, upper) in intervals {
+            match merged.last_mut() {
+                Some((_, last_upper)) if touches(last_upper, &lower) => {
+                    if cmp_upper(&upper, last_upper) == Ordering::Greater {
+                        *last_upper = upper;
+                    }
+                }
+                _ => merged.push((lower, upper)),
+            }
+        }
+        Self { intervals: merged }
+    }
+}
+
+/// The lowest version with the given major, minor and patch, e.g. `1.2.0-0`.
+fn floor(major: u16, minor: u16, patch: u16) -> Version {
+    const ZERO: Prerelease = match Prerelease::parse_const(b"0") {
+        Ok(pre) => pre,
+        Err(_) => panic!("\"0\" is a valid pre-release"),
+    };
+    Version::new(major, minor, patch).with_prerelease(ZERO)
+}
+
+/// The bound below every version of the next major.
+fn next_major(major: u16) -> Bound<Version> {
+    match major.checked_add(1) {
+        Some(major) => Excluded(floor(major, 0, 0)),
+        None => Unbounded,
+    }
+}
+
+/// The bound below every version of the next minor.
+fn next_minor(major: u16, minor: u16) -> Bound<Version> {
+    match minor.checked_add(1) {
+        Some(minor) => Excluded(floor(major, minor, 0)),
+        None => next_major(major),
+    }
+}
+
+/// The bound below every version of the next patch.
+fn next_patch(major: u16, minor: u16, patch: u16) -> Bound<Version> {
+    match patch.checked_add(1) {
+        Some(patch) => Excluded(floor(major, minor, patch)),
+        None => next_minor(major, minor),
+    }
+}
+
+/// The smallest version that is greater than `version`, if there is one that fits.
+fn successor(version: &Version) -> Option<Version> {
+    if version.is_prerelease() {
+        let pre = Prerelease::new(&format!("{}.0", version.prerelease())).ok()?;
+        return Some(version.with_prerelease(pre));
+    }
+    match next_patch(version.major(), version.minor(), version.patch()) {
+        Excluded(next) => Some(next),
+        _ => None,
+    }
+}
+
+fn without_build(version: Version) -> Version {
+    version.with_build(BuildMetadata::EMPTY)
+}
+
+/// Turns the bound on one side of an interval into the bound on the other side of
+/// the gap next to it. `None` if there is no gap.
+fn flip(bound: &Bound<Version>) -> Option<Bound<Version>> {
+    match bound {
+        Included(version) => Some(Excluded(*version)),
+        Excluded(version) => Some(Included(*version)),
+        Unbounded => None,
+    }
+}
+
+fn cmp_lower(a: &Bound<Version>, b: &Bound<Version>) -> Ordering {
+    match (a, b) {
+        (Unbounded, Unbounded) => Ordering::Equal,
+        (Unbounded, _) => Ordering::Less,
+        (_, Unbounded) => Ordering::Greater,
+        (Included(x) | Excluded(x), Included(y) | Excluded(y)) => x
+            .cmp(y)
+            .then_with(|| matches!(a, Excluded(_)).cmp(&matches!(b, Excluded(_)))),
+    }
+}
+
+fn cmp_upper(a: &Bound<Version>, b: &Bound<Version>) -> Ordering {
+    match (a, b) {
+        (Unbounded, Unbounded) => Ordering::Equal,
+        (Unbounded, _) => Ordering::Greater,
+        (_, Unbounded) => Ordering::Less,
+        (Included(x) | Excluded(x), Included(y) | Excluded(y)) => x
+            .cmp(y)
+            .then_with(|| matches!(a, Included(_)).cmp(&matches!(b, Included(_)))),
+    }
+}
+
+fn is_empty_interval(lower: &Bound<Version>, upper: &Bound<Version>) -> bool {
+    match (lower, upper) {
+        (Unbounded, Unbounded) => false,
+        (Unbounded, Included(_)) => false,
+        (Unbounded, Excluded(upper)) => *upper == floor(0, 0, 0),
+        (Included(_), Unbounded) => false,
+        (Excluded(lower), Unbounded) => successor(lower).is_none() && !lower.is_prerelease(),
+        (Included(lower), Included(upper)) => lower > upper,
+        (Included(lower), Excluded(upper)) | (Excluded(lower), Included(upper)) => lower >= upper,
+        (Excluded(lower), Excluded(upper)) => {
+            lower >= upper || successor(lower).is_some_and(|next| next == *upper)
+        }
+    }
+}
+
+/// Checks if an interval ending at `upper` and one starting at `lower` leave no
+/// version between them.
+fn touches(upper: &Bound<Version>, lower: &Bound<Version>) -> bool {
+    match (upper, lower) {
+        (Unbounded, _) | (_, Unbounded) => true,
+        (Included(upper), Included(lower)) => {
+            lower <= upper || successor(upper).is_some_and(|next| next == *lower)
+        }
+        (Included(upper), Excluded(lower)) | (Excluded(upper), Included(lower)) => lower <= upper,
+        (Excluded(upper), Excluded(lower)) => lower < upper,
+    }
+}
+
+impl From<&Comparator> for VersionSet {
+    fn from(comparator: &Comparator) -> Self {
+        let major = comparator.major();
+        let (low, high) = match (comparator.minor(), comparator.patch()) {
+            (Some(minor), Some(patch)) => {
+                let version =
+                    Version::new(major, minor, patch).with_prerelease(*comparator.prerelease());
+                (version, Included(version))
+            }
+            (Some(minor), None) => (floor(major, minor, 0), next_minor(major, minor)),
+            (None, _) => (floor(major, 0, 0), next_major(major)),
+        };
+
+        let (lower, upper) = match comparator.op() {
+            Op::Exact | Op::Wildcard => (Included(low), high),
+            Op::Greater => match flip(&high) {
+                Some(lower) => (lower, Unbounded),
+                None => return Self::empty(),
+            },
+            Op::GreaterEq => (Included(low), Unbounded),
+            Op::Less => (Unbounded, Excluded(low)),
+            Op::LessEq => (Unbounded, high),
+            Op::Tilde => match comparator.minor() {
+                Some(minor) => (Included(low), next_minor(major, minor)),
+                None => (Included(low), next_major(major)),
+            },
+            Op::Caret => {
+                let upper = match (comparator.minor(), comparator.patch()) {
+                    (Some(minor), None) if major == 0 => next_minor(major, minor),
+                    (Some(0), Some(patch)) if major == 0 => next_patch(major, 0, patch),
+                    (Some(minor), Some(_)) if major == 0 => next_minor(major, minor),
+                    _ => next_major(major),
+                };
+                (Included(low), upper)
+            }
+        };
+        Self::interval(lower, upper)
+    }
+}
+
+impl From<&VersionReq> for VersionSet {
+    fn from(req: &VersionReq) -> Self {
+        req.comparators()
+            .iter()
+            .fold(Self::full(), |set, comparator| {
+                set.intersection(&comparator.into())
+            })
+    }
+}
+
+impl From<&npm::Comparator> for VersionSet {
+    fn from(comparator: &npm::Comparator) -> Self {
+        let version = *comparator.version();
+        match comparator.op() {
+            Operator::Eq => Self::exact(version),
+            Operator::Less => Self::interval(Unbounded, Excluded(version)),
+            Operator::LessEq => Self::interval(Unbounded, Included(version)),
+            Operator::Greater => Self::interval(Excluded(version), Unbounded),
+            Operator::GreaterEq => Self::interval(Included(version), Unbounded),
+        }
+    }
+}
+
+impl From<&npm::Range> for VersionSet {
+    fn from(range: &npm::Range) -> Self {
+        range.sets().iter().fold(Self::empty(), |union, set| {
+            let set = set.iter().fold(Self::full(), |set, comparator| {
+                set.intersection(&comparator.into())
+            });
+            union.union(&set)
+        })
+    }
+}
+
+impl fmt::Display for VersionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.intervals.is_empty() {
+            // Nothing is below the lowest possible version.
+            return write!(f, "<{}", floor(0, 0, 0));
+        }
+        for (index, (lower, upper)) in self.intervals.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" || ")?;
+            }
+            match (lower, upper) {
+                (Unbounded, Unbounded) => f.write_str("*")?,
+                (Included(lower), Included(upper)) if lower == upper => write!(f, "={lower}")?,
+                _ => {
+                    match lower {
+                        Included(version) => write!(f, ">={version}")?,
+                        Excluded(version) => write!(f, ">{version}")?,
+                        Unbounded => {}
+                    }
+                    if lower != &Unbounded && upper != &Unbounded {
+                        f.write_str(", ")?;
+                    }
+                    match upper {
+                        Included(version) => write!(f, "<={version}")?,
+                        Excluded(version) => write!(f, "<{version}")?,
+                        Unbounded => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for VersionSet {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split("||").try_fold(Self::empty(), |set, part| {
+            let req = VersionReq::parse(part.trim())?;
+            Ok(set.union(&(&req).into()))
+        })
+    }
+}
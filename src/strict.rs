@@ -0,0 +1,49 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use crate::{Component, NumberErrorKind, Version, VersionError};
+
+impl Version {
+    /// Parses a version string that follows the SemVer 2.0 grammar exactly.
+    ///
+    /// `FromStr` accepts leading zeros in the major, minor and patch versions, as in
+    /// `01.002.3`. This parser rejects them, and is meant for API boundaries where only
+    /// canonical versions should be accepted.
+    ///
+    /// # Parameters
+    /// - `s`: The version string, e.g. `"1.2.3-rc.1+build.5"`.
+    ///
+    /// # Returns
+    /// A `Version` instance, or a `VersionError` if `s` is not a valid SemVer version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use app_version::Version;
+    ///
+    /// assert!(Version::parse_strict("1.2.3-rc.1").is_ok());
+    /// assert!(Version::parse_strict("01.002.3").is_err());
+    /// assert!(Version::parse_strict("1.2.3-rc.01").is_err());
+    /// ```
+    pub fn parse_strict(s: &str) -> Result<Self, VersionError> {
+        let version = s.parse::<Version>()?;
+
+        let core = &s[..s.find(['-', '+']).unwrap_or(s.len())];
+        let components = [Component::Major, Component::Minor, Component::Patch];
+        let mut offset = 0;
+        for (component, text) in components.into_iter().zip(core.split('.')) {
+            if text.len() > 1 && text.starts_with('0') {
+                return Err(VersionError::InvalidNumber {
+                    component,
+                    offset,
+                    text: text.to_string(),
+                    kind: NumberErrorKind::LeadingZero,
+                });
+            }
+            offset += text.len() + 1;
+        }
+
+        Ok(version)
+    }
+}
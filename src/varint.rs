@@ -0,0 +1,215 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Compact variable-length encoding of [`Version`].
+//!
+//! All integers are unsigned LEB128: seven bits per byte, least significant
+//! group first, with the high bit set on every byte except the last. Only the
+//! shortest encoding of a value is accepted when decoding.
+//!
+//! | Field            | Encoding                                              |
+//! |------------------|-------------------------------------------------------|
+//! | `major`          | LEB128                                                |
+//! | `minor`          | LEB128                                                |
+//! | `patch` + flags  | LEB128 of `patch << 2 \| flags`                        |
+//! | pre-release      | if flags bit 0: LEB128 length, then the ASCII bytes   |
+//! | build metadata   | if flags bit 1: LEB128 length, then the ASCII bytes   |
+//!
+//! A plain release such as `0.1.2` therefore takes three bytes, and no version
+//! takes more than [`Version::MAX_VARINT_SIZE`] bytes.
+use crate::{BuildMetadata, Prerelease, Version, VersionError};
+use std::fmt;
+
+const FLAG_PRERELEASE: u32 = 0b01;
+const FLAG_BUILD: u32 = 0b10;
+const FLAG_BITS: u32 = 2;
+
+/// The reason a variable-length encoded version could not be decoded.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The input ended before the version was complete.
+    Truncated,
+    /// An integer was not in its shortest form, or was out of range.
+    Overlong,
+    /// The pre-release or build metadata bytes were not valid identifiers.
+    InvalidIdentifiers,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Truncated => write!(f, "input is truncated"),
+            DecodeError::Overlong => write!(f, "integer is overlong or out of range"),
+            DecodeError::InvalidIdentifiers => write!(f, "invalid identifiers"),
+        }
+    }
+}
+
+impl Version {
+    /// The maximum size in bytes of the variable-length encoding.
+    pub const MAX_VARINT_SIZE: usize =
+        3 + 3 + 3 + 1 + Prerelease::MAX_LEN + 1 + BuildMetadata::MAX_LEN;
+
+    /// Returns the size in bytes of the variable-length encoding of this version.
+    pub fn varint_len(&self) -> usize {
+        let mut len = uleb_len(self.major.into())
+            + uleb_len(self.minor.into())
+            + uleb_len(u32::from(self.patch) << FLAG_BITS);
+        for identifiers in [self.pre.as_str(), self.build.as_str()] {
+            if !identifiers.is_empty() {
+                len += uleb_len(identifiers.len() as u32) + identifiers.len();
+            }
+        }
+        len
+    }
+
+    /// Encodes the version with the variable-length encoding.
+    ///
+    /// # Parameters
+    /// - `buf`: The destination, which must hold at least [`Version::varint_len`] bytes.
+    ///
+    /// # Returns
+    /// The number of bytes written, or `VersionError::BufferTooSmall`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use app_version::Version;
+    ///
+    /// let mut buf = [0; Version::MAX_VARINT_SIZE];
+    /// let written = Version::new(0, 1, 2).encode_varint(&mut buf).unwrap();
+    /// assert_eq!(&buf[..written], [0, 1, 8]);
+    /// assert_eq!(Version::decode_varint(&buf[..written]).unwrap(), (Version::new(0, 1, 2), 3));
+    /// ```
+    pub fn encode_varint(&self, buf: &mut [u8]) -> Result<usize, VersionError> {
+        if buf.len() < self.varint_len() {
+            return Err(VersionError::BufferTooSmall);
+        }
+
+        let mut flags = 0;
+        if !self.pre.is_empty() {
+            flags |= FLAG_PRERELEASE;
+        }
+        if !self.build.is_empty() {
+            flags |= FLAG_BUILD;
+        }
+
+        let mut pos = write_uleb(buf, 0, self.major.into());
+        pos = write_uleb(buf, pos, self.minor.into());
+        pos = write_uleb(buf, pos, u32::from(self.patch) << FLAG_BITS | flags);
+        for identifiers in [self.pre.as_str(), self.build.as_str()] {
+            if !identifiers.is_empty() {
+                pos = write_uleb(buf, pos, identifiers.len() as u32);
+                buf[pos..pos + identifiers.len()].copy_from_slice(identifiers.as_bytes());
+                pos += identifiers.len();
+            }
+        }
+        Ok(pos)
+    }
+
+    /// Decodes a version from the variable-length encoding.
+    ///
+    /// Bytes after the encoded version are left untouched.
+    ///
+    /// # Parameters
+    /// - `buf`: The encoded bytes.
+    ///
+    /// # Returns
+    /// The decoded `Version` and the number of bytes consumed, or `VersionError::Decode`.
+    pub fn decode_varint(buf: &[u8]) -> Result<(Self, usize), VersionError> {
+        let (major, pos) = read_uleb(buf, 0, u16::MAX.into())?;
+        let (minor, pos) = read_uleb(buf, pos, u16::MAX.into())?;
+        let (patch_and_flags, mut pos) = read_uleb(
+            buf,
+            pos,
+            u32::from(u16::MAX) << FLAG_BITS | FLAG_PRERELEASE | FLAG_BUILD,
+        )?;
+        let flags = patch_and_flags & (FLAG_PRERELEASE | FLAG_BUILD);
+
+        let mut version = Version::new(
+            major as u16,
+            minor as u16,
+            (patch_and_flags >> FLAG_BITS) as u16,
+        );
+        if flags & FLAG_PRERELEASE != 0 {
+            let identifiers;
+            (identifiers, pos) = read_identifiers(buf, pos, Prerelease::MAX_LEN)?;
+            version.pre = Prerelease::new(identifiers)
+                .ok()
+                .filter(|pre| !pre.is_empty())
+                .ok_or(VersionError::Decode(DecodeError::InvalidIdentifiers))?;
+        }
+        if flags & FLAG_BUILD != 0 {
+            let identifiers;
+            (identifiers, pos) = read_identifiers(buf, pos, BuildMetadata::MAX_LEN)?;
+            version.build = BuildMetadata::new(identifiers)
+                .ok()
+                .filter(|build| !build.is_empty())
+                .ok_or(VersionError::Decode(DecodeError::InvalidIdentifiers))?;
+        }
+        Ok((version, pos))
+    }
+}
+
+const fn uleb_len(value: u32) -> usize {
+    let mut len = 1;
+    let mut value = value >> 7;
+    while value != 0 {
+        len += 1;
+        value >>= 7;
+    }
+    len
+}
+
+fn write_uleb(buf: &mut [u8], mut pos: usize, mut value: u32) -> usize {
+    loop {
+        let low = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[pos] = low;
+            return pos + 1;
+        }
+        buf[pos] = low | 0x80;
+        pos += 1;
+    }
+}
+
+fn read_uleb(buf: &[u8], mut pos: usize, max: u32) -> Result<(u32, usize), VersionError> {
+    let mut value: u32 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *buf
+            .get(pos)
+            .ok_or(VersionError::Decode(DecodeError::Truncated))?;
+        pos += 1;
+        let low = u32::from(byte & 0x7f);
+        if shift > 0 && byte == 0 {
+            // A trailing zero group means the value had a shorter encoding.
+            return Err(VersionError::Decode(DecodeError::Overlong));
+        }
+        if shift >= u32::BITS || low > (max >> shift) {
+            return Err(VersionError::Decode(DecodeError::Overlong));
+        }
+        value |= low << shift;
+        if byte & 0x80 == 0 {
+            if value > max {
+                return Err(VersionError::Decode(DecodeError::Overlong));
+            }
+            return Ok((value, pos));
+        }
+        shift += 7;
+    }
+}
+
+fn read_identifiers(buf: &[u8], pos: usize, max_len: usize) -> Result<(&str, usize), VersionError> {
+    let (len, pos) = read_uleb(buf, pos, max_len as u32)?;
+    let end = pos + len as usize;
+    let bytes = buf
+        .get(pos..end)
+        .ok_or(VersionError::Decode(DecodeError::Truncated))?;
+    let identifiers = std::str::from_utf8(bytes)
+        .map_err(|_| VersionError::Decode(DecodeError::InvalidIdentifiers))?;
+    Ok((identifiers, end))
+}
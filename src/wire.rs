@@ -0,0 +1,115 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Fixed-size binary encoding of [`Version`].
+//!
+//! The encoding is always [`Version::ENCODED_SIZE`] bytes:
+//!
+//! | Offset | Size | Field   |
+//! |--------|------|---------|
+//! | 0      | 2    | `major` |
+//! | 2      | 2    | `minor` |
+//! | 4      | 2    | `patch` |
+//!
+//! Each field is an unsigned 16-bit integer in network byte order (big-endian),
+//! or little-endian when using the `_le_` variants. The same layout can be
+//! declared in C as `struct { uint16_t major, minor, patch; }` with the fields
+//! converted using `htons`/`ntohs`.
+//!
+//! Only the release numbers are encoded; the pre-release and build metadata are
+//! not part of this layout and are dropped.
+use crate::Version;
+use std::io::{Read, Result, Write};
+
+impl Version {
+    /// The size in bytes of the fixed binary encoding.
+    pub const ENCODED_SIZE: usize = 6;
+
+    /// Encodes the version in network byte order (big-endian).
+    ///
+    /// # Returns
+    /// The encoded bytes, see the [layout](crate::wire).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use app_version::Version;
+    ///
+    /// let bytes = Version::new(1, 2, 0x0304).to_bytes();
+    /// assert_eq!(bytes, [0, 1, 0, 2, 3, 4]);
+    /// assert_eq!(Version::from_bytes(bytes), Version::new(1, 2, 0x0304));
+    /// ```
+    pub const fn to_bytes(&self) -> [u8; Self::ENCODED_SIZE] {
+        let major = self.major.to_be_bytes();
+        let minor = self.minor.to_be_bytes();
+        let patch = self.patch.to_be_bytes();
+        [major[0], major[1], minor[0], minor[1], patch[0], patch[1]]
+    }
+
+    /// Decodes a version encoded in network byte order (big-endian).
+    ///
+    /// # Parameters
+    /// - `bytes`: The encoded bytes, see the [layout](crate::wire).
+    ///
+    /// # Returns
+    /// A `Version` instance.
+    pub const fn from_bytes(bytes: [u8; Self::ENCODED_SIZE]) -> Self {
+        Self::new(
+            u16::from_be_bytes([bytes[0], bytes[1]]),
+            u16::from_be_bytes([bytes[2], bytes[3]]),
+            u16::from_be_bytes([bytes[4], bytes[5]]),
+        )
+    }
+
+    /// Encodes the version in little-endian byte order.
+    ///
+    /// # Returns
+    /// The encoded bytes, see the [layout](crate::wire).
+    pub const fn to_le_bytes(&self) -> [u8; Self::ENCODED_SIZE] {
+        let major = self.major.to_le_bytes();
+        let minor = self.minor.to_le_bytes();
+        let patch = self.patch.to_le_bytes();
+        [major[0], major[1], minor[0], minor[1], patch[0], patch[1]]
+    }
+
+    /// Decodes a version encoded in little-endian byte order.
+    ///
+    /// # Parameters
+    /// - `bytes`: The encoded bytes, see the [layout](crate::wire).
+    ///
+    /// # Returns
+    /// A `Version` instance.
+    pub const fn from_le_bytes(bytes: [u8; Self::ENCODED_SIZE]) -> Self {
+        Self::new(
+            u16::from_le_bytes([bytes[0], bytes[1]]),
+            u16::from_le_bytes([bytes[2], bytes[3]]),
+            u16::from_le_bytes([bytes[4], bytes[5]]),
+        )
+    }
+
+    /// Writes the version in network byte order (big-endian).
+    ///
+    /// # Parameters
+    /// - `writer`: The destination of the encoded bytes.
+    ///
+    /// # Returns
+    /// An error if writing failed.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    /// Reads a version encoded in network byte order (big-endian).
+    ///
+    /// # Parameters
+    /// - `reader`: The source of the encoded bytes.
+    ///
+    /// # Returns
+    /// The decoded `Version`, or an error if fewer than [`Version::ENCODED_SIZE`] bytes could be read.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut bytes = [0; Self::ENCODED_SIZE];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes(bytes))
+    }
+}
@@ -0,0 +1,651 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+
+//! Coordinated version bumps across the members of a Cargo workspace.
+//!
+//! Bumping a crate can break the requirements other members have on it, e.g. a
+//! minor bump of `0.4.2` no longer matches `nimble-protocol = "0.4"`. The [`Plan`]
+//! lists the requirements that must be rewritten, and bumps every member with a
+//! rewritten `[dependencies]` or `[build-dependencies]` requirement, which may in
+//! turn break the requirements of its own dependents. Requirements in
+//! `[dev-dependencies]` are rewritten, but do not change the dependent's version.
+//!
+//! A dependent gets a patch bump, unless the dependency's bump is breaking under
+//! Cargo's caret rules, such as `0.4.2 -> 0.5.0`. The dependent may expose types of
+//! the dependency in its own API, so it gets a breaking bump as well: a major bump,
+//! or a minor bump while it is below 1.0.0.
+//!
+//! Only a requirement with a single `^`, `~` or `=` comparator, such as `"0.4"` or
+//! `"=0.4.2"`, is rewritten. Others, such as `">=0.4, <0.5"` or `"0.4.*"`, and
+//! requirements that can not be parsed, are listed in the plan as needing a manual
+//! edit.
+//!
+//! Members are found from `[workspace].members`, where `*` matches any part of a
+//! single directory name. Requirements in `[workspace.dependencies]` are followed
+//! for members that use `workspace = true`. Target-specific dependency tables are
+//! not changed.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use app_version::workspace::Workspace;
+//! use app_version::BumpLevel;
+//!
+//! let workspace = Workspace::load("Cargo.toml")?;
+//! let plan = workspace.plan(&[("nimble-protocol", BumpLevel::Minor)])?;
+//! print!("{plan}");
+//! workspace.apply(&plan)?;
+//! # Ok::<(), app_version::manifest::ManifestError>(())
+//! ```
+use crate::manifest::{replace_string, Manifest, ManifestError};
+use crate::{BumpLevel, CargoCaret, CompatibilityPolicy, Op, Version, VersionReq};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use toml_edit::Item;
+
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "build-dependencies", "dev-dependencies"];
+const WORKSPACE_DEPENDENCIES: &str = "workspace.dependencies";
+
+/// Why a package is bumped.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BumpReason {
+    /// The bump was asked for.
+    Requested,
+    /// The requirement on this dependency had to be rewritten. The bump is breaking if
+    /// the dependency's bump was.
+    Dependency(String),
+    /// The package inherits the workspace version, which another member bumped.
+    SharedVersion,
+}
+
+impl fmt::Display for BumpReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BumpReason::Requested => write!(f, "requested"),
+            BumpReason::Dependency(name) => write!(f, "depends on {}", name),
+            BumpReason::SharedVersion => write!(f, "shares the workspace version"),
+        }
+    }
+}
+
+/// A version change of one workspace member.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PlannedBump {
+    /// The package name.
+    pub package: String,
+    /// The current version.
+    pub previous: Version,
+    /// The version after the bump.
+    pub version: Version,
+    /// The component that is incremented.
+    pub level: BumpLevel,
+    /// Why the package is bumped.
+    pub reason: BumpReason,
+}
+
+/// A dependency requirement that no longer matches the bumped version.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RequirementUpdate {
+    /// The manifest with the requirement.
+    pub path: PathBuf,
+    /// The table with the requirement, e.g. `"dependencies"` or `"workspace.dependencies"`.
+    pub table: String,
+    /// The key of the dependency in the table.
+    pub dependency: String,
+    /// The current requirement.
+    pub previous: String,
+    /// The version the requirement must accept.
+    pub version: Version,
+    /// The requirement after the update, which keeps a leading `^`, `~` or `=`, or
+    /// `None` if the requirement has another form and must be edited by hand.
+    pub requirement: Option<String>,
+}
+
+/// The changes needed to bump some members of a workspace.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Plan {
+    root: PathBuf,
+    bumps: Vec<PlannedBump>,
+    requirements: Vec<RequirementUpdate>,
+}
+
+impl Plan {
+    /// Returns the version changes, in the order the members were found.
+    pub fn bumps(&self) -> &[PlannedBump] {
+        &self.bumps
+    }
+
+    /// Returns the requirements that must be rewritten.
+    pub fn requirements(&self) -> &[RequirementUpdate] {
+        &self.requirements
+    }
+
+    /// Returns `true` if the plan changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.bumps.is_empty() && self.requirements.is_empty()
+    }
+}
+
+impl fmt::Display for Plan {
+    /// Writes one line per change, with manifest paths relative to the workspace root.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "Nothing to change");
+        }
+        for bump in &self.bumps {
+            writeln!(
+                f,
+                "Bump {} {} -> {} ({}, {})",
+                bump.package, bump.previous, bump.version, bump.level, bump.reason
+            )?;
+        }
+        for update in &self.requirements {
+            let path = update.path.strip_prefix(&self.root).unwrap_or(&update.path);
+            match &update.requirement {
+                Some(requirement) => writeln!(
+                    f,
+                    "Update {} [{}] {}: \"{}\" -> \"{}\"",
+                    path.display(),
+                    update.table,
+                    update.dependency,
+                    update.previous,
+                    requirement
+                )?,
+                None => writeln!(
+                    f,
+                    "Edit {} [{}] {} by hand: \"{}\" does not allow {}",
+                    path.display(),
+                    update.table,
+                    update.dependency,
+                    update.previous,
+                    update.version
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Member {
+    manifest: usize,
+    name: String,
+    version: Version,
+    inherited: bool,
+}
+
+/// A requirement on a member, in a dependency table of one of the manifests.
+#[derive(Debug, Clone)]
+struct Requirement {
+    manifest: usize,
+    table: &'static str,
+    key: String,
+    target: usize,
+    text: String,
+}
+
+/// A member that depends on another member through a requirement.
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    dependent: usize,
+    requirement: usize,
+    affects_version: bool,
+}
+
+/// Members that inherit the workspace version share one slot.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+enum Slot {
+    Package(usize),
+    Shared,
+}
+
+/// The manifests of a workspace and the requirements between its members.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    /// The root manifest comes first, and is also the manifest of a root package.
+    manifests: Vec<Manifest>,
+    members: Vec<Member>,
+    requirements: Vec<Requirement>,
+    edges: Vec<Edge>,
+}
+
+impl Workspace {
+    /// Loads the workspace and all of its member manifests.
+    ///
+    /// # Parameters
+    /// - `path`: The `Cargo.toml` of the workspace root or of any member.
+    ///
+    /// # Returns
+    /// The `Workspace`, or a `ManifestError` if a manifest can not be read or a
+    /// member has no valid version.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        let root = Manifest::open(path)?.find_workspace_root()?;
+        let root_dir = directory_of(root.path());
+        let mut manifests = vec![root];
+
+        let workspace = manifests[0].document().get("workspace");
+        let patterns = string_array(workspace.and_then(|workspace| workspace.get("members")));
+        let excluded: Vec<PathBuf> =
+            string_array(workspace.and_then(|workspace| workspace.get("exclude")))
+                .iter()
+                .map(|exclude| normalize(&root_dir.join(exclude)))
+                .collect();
+
+        let mut dirs = BTreeSet::new();
+        for pattern in &patterns {
+            for dir in expand(&root_dir, pattern)? {
+                if dir != root_dir && !excluded.contains(&normalize(&dir)) {
+                    dirs.insert(dir);
+                }
+            }
+        }
+        for dir in dirs {
+            manifests.push(Manifest::open(dir.join("Cargo.toml"))?);
+        }
+
+        let mut members = Vec::new();
+        for (index, manifest) in manifests.iter().enumerate() {
+            let Some(name) = manifest.name() else {
+                continue;
+            };
+            let inherited = manifest.is_version_inherited();
+            let version = if inherited {
+                manifests[0].workspace_version()?
+            } else {
+                manifest.version()?
+            };
+            members.push(Member {
+                manifest: index,
+                name: name.to_string(),
+                version,
+                inherited,
+            });
+        }
+
+        let mut workspace = Self {
+            manifests,
+            members,
+            requirements: Vec::new(),
+            edges: Vec::new(),
+        };
+        workspace.find_requirements();
+        Ok(workspace)
+    }
+
+    /// Returns the path of the root manifest.
+    pub fn root(&self) -> &Path {
+        self.manifests[0].path()
+    }
+
+    /// Returns the package names of the members.
+    pub fn packages(&self) -> impl Iterator<Item = &str> {
+        self.members.iter().map(|member| member.name.as_str())
+    }
+
+    /// Returns the current version of a member, resolving `version.workspace = true`.
+    pub fn version(&self, package: &str) -> Option<Version> {
+        let index = self.member_index(package)?;
+        Some(self.members[index].version)
+    }
+
+    /// Plans the bumps of the given members and everything they force.
+    ///
+    /// # Parameters
+    /// - `bumps`: The package names to bump, and the component to increment for each.
+    ///
+    /// # Returns
+    /// The `Plan`, or `ManifestError::UnknownPackage` if a name is not a member.
+    pub fn plan(&self, bumps: &[(&str, BumpLevel)]) -> Result<Plan, ManifestError> {
+        let mut levels = BTreeMap::new();
+        let mut requested = BTreeSet::new();
+        for &(package, level) in bumps {
+            let index = self
+                .member_index(package)
+                .ok_or_else(|| ManifestError::UnknownPackage(package.to_string()))?;
+            let slot = levels.entry(self.slot(index)).or_insert(level);
+            *slot = (*slot).max(level);
+            requested.insert(index);
+        }
+
+        let mut dependencies = BTreeMap::new();
+        let versions = loop {
+            let versions = self.bumped_versions(&levels)?;
+            let mut changed = false;
+            for edge in &self.edges {
+                if !edge.affects_version || self.matches(edge.requirement, &versions) {
+                    continue;
+                }
+                let target = self.requirements[edge.requirement].target;
+                let dependent = &self.members[edge.dependent].version;
+                let level =
+                    if CargoCaret.is_compatible(&self.members[target].version, &versions[target]) {
+                        BumpLevel::Patch
+                    } else if dependent.major() > 0 {
+                        BumpLevel::Major
+                    } else {
+                        BumpLevel::Minor
+                    };
+                let slot = self.slot(edge.dependent);
+                if levels.get(&slot).is_none_or(|&current| current < level) {
+                    levels.insert(slot, level);
+                    changed = true;
+                }
+                dependencies
+                    .entry(edge.dependent)
+                    .or_insert_with(|| self.members[target].name.clone());
+            }
+            if !changed {
+                break versions;
+            }
+        };
+
+        let mut planned = Vec::new();
+        for (index, member) in self.members.iter().enumerate() {
+            if versions[index] == member.version {
+                continue;
+            }
+            let reason = if requested.contains(&index) {
+                BumpReason::Requested
+            } else if let Some(dependency) = dependencies.get(&index) {
+                BumpReason::Dependency(dependency.clone())
+            } else {
+                BumpReason::SharedVersion
+            };
+            planned.push(PlannedBump {
+                package: member.name.clone(),
+                previous: member.version,
+                version: versions[index],
+                level: levels[&self.slot(index)],
+                reason,
+            });
+        }
+
+        let mut requirements = Vec::new();
+        for (index, requirement) in self.requirements.iter().enumerate() {
+            if self.matches(index, &versions) {
+                continue;
+            }
+            requirements.push(RequirementUpdate {
+                path: self.manifests[requirement.manifest].path().to_path_buf(),
+                table: requirement.table.to_string(),
+                dependency: requirement.key.clone(),
+                previous: requirement.text.clone(),
+                version: versions[requirement.target],
+                requirement: rewrite_requirement(&requirement.text, &versions[requirement.target]),
+            });
+        }
+
+        Ok(Plan {
+            root: directory_of(self.root()),
+            bumps: planned,
+            requirements,
+        })
+    }
+
+    /// Writes a plan to the manifests, keeping their formatting.
+    ///
+    /// # Parameters
+    /// - `plan`: A plan made by [`Workspace::plan`] for this workspace.
+    ///
+    /// # Returns
+    /// The paths of the manifests that were written. Requirements that must be edited
+    /// by hand are left unchanged.
+    pub fn apply(mut self, plan: &Plan) -> Result<Vec<PathBuf>, ManifestError> {
+        let mut changed = BTreeSet::new();
+        for bump in &plan.bumps {
+            let index = self
+                .member_index(&bump.package)
+                .ok_or_else(|| ManifestError::UnknownPackage(bump.package.clone()))?;
+            let member = &self.members[index];
+            if member.inherited {
+                self.manifests[0].set_workspace_version(&bump.version)?;
+                changed.insert(0);
+            } else {
+                self.manifests[member.manifest].set_version(&bump.version)?;
+                changed.insert(member.manifest);
+            }
+        }
+
+        for update in &plan.requirements {
+            let Some(requirement) = &update.requirement else {
+                continue;
+            };
+            let index = self
+                .manifests
+                .iter()
+                .position(|manifest| manifest.path() == update.path)
+                .ok_or_else(|| {
+                    ManifestError::Io(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("{} is not in the workspace", update.path.display()),
+                    ))
+                })?;
+            let mut item = Some(self.manifests[index].document_mut().as_item_mut());
+            for key in update.table.split('.') {
+                item = item.and_then(|item| item.get_mut(key));
+            }
+            let item = item.and_then(|table| table.get_mut(&update.dependency));
+            match item {
+                Some(item) if item.is_str() => replace_string(Some(item), requirement)?,
+                item => replace_string(item.and_then(|item| item.get_mut("version")), requirement)?,
+            }
+            changed.insert(index);
+        }
+
+        let mut paths = Vec::new();
+        for index in changed {
+            self.manifests[index].save()?;
+            paths.push(self.manifests[index].path().to_path_buf());
+        }
+        Ok(paths)
+    }
+
+    fn member_index(&self, package: &str) -> Option<usize> {
+        self.members
+            .iter()
+            .position(|member| member.name == package)
+    }
+
+    fn slot(&self, member: usize) -> Slot {
+        if self.members[member].inherited {
+            Slot::Shared
+        } else {
+            Slot::Package(member)
+        }
+    }
+
+    fn bumped_versions(
+        &self,
+        levels: &BTreeMap<Slot, BumpLevel>,
+    ) -> Result<Vec<Version>, ManifestError> {
+        let mut versions = Vec::with_capacity(self.members.len());
+        for (index, member) in self.members.iter().enumerate() {
+            versions.push(match levels.get(&self.slot(index)) {
+                Some(&level) => member.version.bumped(level)?,
+                None => member.version,
+            });
+        }
+        Ok(versions)
+    }
+
+    /// Returns `true` if the requirement still matches the version of its target.
+    ///
+    /// A requirement that can not be parsed only matches the unchanged version.
+    fn matches(&self, requirement: usize, versions: &[Version]) -> bool {
+        let requirement = &self.requirements[requirement];
+        let version = &versions[requirement.target];
+        *version == self.members[requirement.target].version
+            || VersionReq::parse(&requirement.text).is_ok_and(|req| req.matches(version))
+    }
+
+    fn find_requirements(&mut self) {
+        let mut workspace_requirements = BTreeMap::new();
+        let root = self.manifests[0].document();
+        let table = root
+            .get("workspace")
+            .and_then(|workspace| workspace.get("dependencies"))
+            .and_then(Item::as_table_like);
+        for (key, item) in table.into_iter().flat_map(|table| table.iter()) {
+            let Some(target) = self.member_index(package_name(key, item)) else {
+                continue;
+            };
+            let Some(text) = requirement_text(item) else {
+                continue;
+            };
+            workspace_requirements.insert(key.to_string(), self.requirements.len());
+            self.requirements.push(Requirement {
+                manifest: 0,
+                table: WORKSPACE_DEPENDENCIES,
+                key: key.to_string(),
+                target,
+                text: text.to_string(),
+            });
+        }
+
+        for dependent in 0..self.members.len() {
+            let document = self.manifests[self.members[dependent].manifest].document();
+            for table_name in DEPENDENCY_TABLES {
+                let table = document.get(table_name).and_then(Item::as_table_like);
+                for (key, item) in table.into_iter().flat_map(|table| table.iter()) {
+                    let inherits = item
+                        .get("workspace")
+                        .and_then(Item::as_bool)
+                        .unwrap_or(false);
+                    let requirement = if inherits {
+                        match workspace_requirements.get(key) {
+                            Some(&requirement) => requirement,
+                            None => continue,
+                        }
+                    } else {
+                        let Some(target) = self.member_index(package_name(key, item)) else {
+                            continue;
+                        };
+                        let Some(text) = requirement_text(item) else {
+                            continue;
+                        };
+                        self.requirements.push(Requirement {
+                            manifest: self.members[dependent].manifest,
+                            table: table_name,
+                            key: key.to_string(),
+                            target,
+                            text: text.to_string(),
+                        });
+                        self.requirements.len() - 1
+                    };
+                    self.edges.push(Edge {
+                        dependent,
+                        requirement,
+                        affects_version: table_name != "dev-dependencies",
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns the package a dependency refers to, which differs from the key when renamed.
+fn package_name<'a>(key: &'a str, item: &'a Item) -> &'a str {
+    item.get("package").and_then(Item::as_str).unwrap_or(key)
+}
+
+/// Returns the version requirement of a dependency, or `None` for path-only dependencies.
+fn requirement_text(item: &Item) -> Option<&str> {
+    item.as_str()
+        .or_else(|| item.get("version").and_then(Item::as_str))
+}
+
+fn string_array(item: Option<&Item>) -> Vec<String> {
+    item.and_then(Item::as_array)
+        .map(|array| {
+            array
+                .iter()
+                .filter_map(|value| value.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Expands a `[workspace].members` entry to the directories it names.
+fn expand(root: &Path, pattern: &str) -> Result<Vec<PathBuf>, ManifestError> {
+    let mut dirs = vec![root.to_path_buf()];
+    for part in pattern
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+    {
+        let Some((prefix, suffix)) = part.split_once('*') else {
+            dirs.iter_mut().for_each(|dir| dir.push(part));
+            continue;
+        };
+        let mut matches = Vec::new();
+        for dir in &dirs {
+            for entry in fs::read_dir(dir)? {
+                let path = entry?.path();
+                let name = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("");
+                if path.is_dir()
+                    && name.len() >= prefix.len() + suffix.len()
+                    && name.starts_with(prefix)
+                    && name.ends_with(suffix)
+                {
+                    matches.push(path);
+                }
+            }
+        }
+        matches.sort();
+        dirs = matches;
+    }
+    if pattern.contains('*') {
+        dirs.retain(|dir| dir.join("Cargo.toml").is_file());
+    }
+    Ok(dirs)
+}
+
+/// Returns the directory of a manifest, which is `.` for a bare `Cargo.toml`.
+fn directory_of(manifest: &Path) -> PathBuf {
+    match manifest.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// Removes `.` components and resolves `..` components without touching the file system,
+/// so `crates/legacy/` and `./crates/tools/../legacy` name the same directory.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Writes `version` in place of a single `^`, `~` or `=` comparator, keeping the operator.
+///
+/// Returns `None` for other requirements, such as `">=0.1, <0.2"` or `"0.4.*"`, which
+/// can not be rewritten without changing what they accept.
+fn rewrite_requirement(previous: &str, version: &Version) -> Option<String> {
+    let req = VersionReq::parse(previous).ok()?;
+    let [comparator] = req.comparators() else {
+        return None;
+    };
+    if !matches!(comparator.op(), Op::Caret | Op::Tilde | Op::Exact) {
+        return None;
+    }
+    let op = ["^", "~", "="]
+        .into_iter()
+        .find(|op| previous.trim_start().starts_with(op))
+        .unwrap_or("");
+    Some(format!("{}{}", op, version))
+}
@@ -0,0 +1,98 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::{BumpLevel, Component, Version, VersionError};
+
+const MAX: u16 = u16::MAX;
+
+#[test]
+fn bumped_resets_lower_components() {
+    let version: Version = "1.2.3-rc.1+build.7".parse().unwrap();
+    assert_eq!(
+        version.bumped(BumpLevel::Patch).unwrap(),
+        Version::new(1, 2, 4)
+    );
+    assert_eq!(
+        version.bumped(BumpLevel::Minor).unwrap(),
+        Version::new(1, 3, 0)
+    );
+    assert_eq!(
+        version.bumped(BumpLevel::Major).unwrap(),
+        Version::new(2, 0, 0)
+    );
+}
+
+#[test]
+fn checked_increment_overflow() {
+    let version = Version::new(MAX, MAX, MAX);
+    assert!(matches!(
+        version.checked_increment_patch(),
+        Err(VersionError::Overflow(Component::Patch))
+    ));
+    assert!(matches!(
+        version.checked_increment_minor(),
+        Err(VersionError::Overflow(Component::Minor))
+    ));
+    assert!(matches!(
+        version.checked_increment_major(),
+        Err(VersionError::Overflow(Component::Major))
+    ));
+    assert_eq!(
+        version.checked_increment_patch().unwrap_err().to_string(),
+        "The patch version overflowed"
+    );
+}
+
+#[test]
+fn checked_increment_below_max() {
+    assert_eq!(
+        Version::new(1, 2, MAX - 1)
+            .checked_increment_patch()
+            .unwrap(),
+        Version::new(1, 2, MAX)
+    );
+    assert_eq!(
+        Version::new(1, 2, MAX).checked_increment_minor().unwrap(),
+        Version::new(1, 3, 0)
+    );
+    assert_eq!(
+        Version::new(1, MAX, MAX).checked_increment_major().unwrap(),
+        Version::new(2, 0, 0)
+    );
+}
+
+#[test]
+fn saturating_increment() {
+    let at_max = Version::new(1, 2, MAX);
+    assert_eq!(at_max.saturating_increment_patch(), at_max);
+    assert_eq!(at_max.saturating_increment_minor(), Version::new(1, 3, 0));
+    assert_eq!(
+        Version::new(MAX, 4, 5).saturating_increment_major(),
+        Version::new(MAX, 4, 5)
+    );
+    assert_eq!(
+        Version::new(MAX, 4, 5).saturating_bumped(BumpLevel::Minor),
+        Version::new(MAX, 5, 0)
+    );
+
+    // Like `bumped`, saturating clears the pre-release and build metadata.
+    let prerelease: Version = "1.2.65535-rc.1+build.7".parse().unwrap();
+    assert_eq!(prerelease.saturating_increment_patch(), at_max);
+    assert_eq!(
+        prerelease.saturating_bumped(BumpLevel::Minor),
+        prerelease.bumped(BumpLevel::Minor).unwrap()
+    );
+}
+
+#[test]
+#[should_panic(expected = "patch version is already u16::MAX")]
+fn increment_patch_panics_at_max() {
+    Version::new(1, 2, MAX).increment_patch();
+}
+
+#[test]
+#[should_panic(expected = "major version is already u16::MAX")]
+fn increment_major_panics_at_max() {
+    Version::new(MAX, 0, 0).increment_major();
+}
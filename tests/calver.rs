@@ -0,0 +1,160 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::calver::{CalVer, Format, Segment};
+use app_version::{Version, VersionError};
+
+fn layout(s: &str) -> Format {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parse_format() {
+    let format = layout("YYYY.0M.MICRO");
+    assert_eq!(
+        format.segments(),
+        [Segment::FullYear, Segment::PaddedMonth, Segment::Micro]
+    );
+    assert_eq!(format.to_string(), "YYYY.0M.MICRO");
+
+    for s in ["YY.0M.0D", "0Y.MM", "YYYY.MICRO", "YY.MM.DD.MICRO"] {
+        assert_eq!(Format::parse(s).unwrap().to_string(), s);
+    }
+}
+
+#[test]
+fn invalid_format() {
+    for s in [
+        "",
+        "YYY",
+        "0M.YYYY",
+        "YYYY.0D",
+        "YYYY.MM.MM",
+        "YYYY.MICRO.MM",
+        "yyyy",
+    ] {
+        assert_eq!(
+            Format::parse(s),
+            Err(VersionError::InvalidCalVerFormat),
+            "{s}"
+        );
+    }
+}
+
+#[test]
+fn parse_and_display() {
+    let version = CalVer::parse("2026.10.3", layout("YYYY.0M.MICRO")).unwrap();
+    assert_eq!(version.year(), 2026);
+    assert_eq!(version.month(), Some(10));
+    assert_eq!(version.day(), None);
+    assert_eq!(version.micro(), Some(3));
+    assert_eq!(version.to_string(), "2026.10.3");
+
+    let short = CalVer::parse("26.10", layout("YY.MM")).unwrap();
+    assert_eq!(short.year(), 2026);
+    assert_eq!(short.micro(), None);
+    assert_eq!(short.to_string(), "26.10");
+
+    let padded = CalVer::new(layout("0Y.0M.0D"), 2006, 2, 3, 0).unwrap();
+    assert_eq!(padded.to_string(), "06.02.03");
+    assert_eq!(CalVer::parse("06.02.03", padded.format()), Ok(padded));
+}
+
+#[test]
+fn padding_must_match_format() {
+    assert_eq!(
+        CalVer::parse("2026.3.0", layout("YYYY.0M.MICRO")),
+        Err(VersionError::InvalidCalVer)
+    );
+    assert_eq!(
+        CalVer::parse("2026.03", layout("YYYY.MM")),
+        Err(VersionError::InvalidCalVer)
+    );
+    assert_eq!(
+        CalVer::parse("2026.10.+3", layout("YYYY.0M.MICRO")),
+        Err(VersionError::InvalidCalVer)
+    );
+    assert_eq!(
+        CalVer::parse("2026.10", layout("YYYY.0M.MICRO")),
+        Err(VersionError::InvalidComponentCount {
+            expected: 3,
+            found: 2
+        })
+    );
+}
+
+#[test]
+fn validates_dates() {
+    let format = layout("YYYY.0M.0D");
+    assert!(CalVer::parse("2024.02.29", format).is_ok());
+    for s in [
+        "2026.02.29",
+        "1900.02.29",
+        "2026.04.31",
+        "2026.00.01",
+        "2026.13.01",
+        "0.01.01",
+    ] {
+        assert_eq!(
+            CalVer::parse(s, format),
+            Err(VersionError::InvalidDate),
+            "{s}"
+        );
+    }
+    assert!(CalVer::parse("2000.02.29", format).is_ok());
+    assert_eq!(
+        CalVer::new(layout("YY.MM"), 1999, 12, 0, 0),
+        Err(VersionError::InvalidDate)
+    );
+}
+
+#[test]
+fn ordering() {
+    let format = layout("YY.0M.MICRO");
+    let mut versions: Vec<CalVer> = ["26.10.3", "25.12.0", "26.10.12", "26.02.7"]
+        .iter()
+        .map(|s| CalVer::parse(s, format).unwrap())
+        .collect();
+    versions.sort();
+    let sorted: Vec<String> = versions.iter().map(ToString::to_string).collect();
+    assert_eq!(sorted, ["25.12.0", "26.02.7", "26.10.3", "26.10.12"]);
+}
+
+#[test]
+fn version_conversion() {
+    let format = layout("YY.0M.MICRO");
+    let calver = CalVer::parse("26.10.3", format).unwrap();
+    let version = calver.to_version().unwrap();
+    assert_eq!(version, Version::new(26, 10, 3));
+    assert_eq!(CalVer::from_version(&version, format), Ok(calver));
+
+    let monthly = layout("YYYY.MM");
+    assert_eq!(
+        CalVer::from_version(&Version::new(2026, 10, 0), monthly)
+            .unwrap()
+            .to_string(),
+        "2026.10"
+    );
+    assert_eq!(
+        CalVer::from_version(&Version::new(2026, 10, 1), monthly),
+        Err(VersionError::InvalidCalVer)
+    );
+    assert_eq!(
+        CalVer::from_version(&Version::new(2026, 13, 0), monthly),
+        Err(VersionError::InvalidDate)
+    );
+    assert_eq!(
+        CalVer::from_version(&"2026.10.0-rc.1".parse().unwrap(), monthly),
+        Err(VersionError::InvalidCalVer)
+    );
+
+    let daily = CalVer::parse("2026.10.18.2", layout("YYYY.0M.0D.MICRO")).unwrap();
+    assert_eq!(
+        daily.to_version(),
+        Err(VersionError::InvalidComponentCount {
+            expected: 3,
+            found: 4
+        })
+    );
+}
@@ -0,0 +1,299 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn app_version(args: &[&str], stdin: &str) -> (i32, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_app-version"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .trim_end()
+            .to_string(),
+    )
+}
+
+fn run(args: &[&str]) -> (i32, String) {
+    app_version(args, "")
+}
+
+#[test]
+fn bump() {
+    assert_eq!(run(&["bump", "minor", "1.2.3-rc.1"]), (0, "1.3.0".into()));
+    assert_eq!(
+        run(&["--json", "bump", "major", "1.2.3"]),
+        (0, r#"{"version":"2.0.0"}"#.into())
+    );
+    assert_eq!(run(&["bump", "patch", "1.2.65535"]).0, 65);
+    assert_eq!(run(&["bump", "huge", "1.2.3"]).0, 64);
+}
+
+#[test]
+fn compare_exit_codes() {
+    assert_eq!(
+        run(&["compare", "1.2.3", "1.2.3+build"]),
+        (0, "equal".into())
+    );
+    assert_eq!(run(&["compare", "1.2.3", "1.10.0"]), (1, "less".into()));
+    assert_eq!(
+        run(&["compare", "1.2.3", "1.2.3-rc.1", "--json"]),
+        (2, r#"{"ordering":"greater"}"#.into())
+    );
+}
+
+#[test]
+fn satisfies() {
+    assert_eq!(
+        run(&["satisfies", "1.4.0", ">=1.2, <2"]),
+        (0, "true".into())
+    );
+    assert_eq!(run(&["satisfies", "2.0.0", "^1.2"]), (1, "false".into()));
+    assert_eq!(
+        run(&["--json", "satisfies", "--npm", "1.2.7", "1.2.x || >=2"]),
+        (0, r#"{"satisfies":true}"#.into())
+    );
+    assert_eq!(run(&["satisfies", "1.0.0", ">>1"]).0, 65);
+}
+
+#[test]
+fn sort_and_max() {
+    let input = "1.10.0\n\n1.2.0\n1.2.0-rc.1\n";
+    assert_eq!(
+        app_version(&["sort"], input),
+        (0, "1.2.0-rc.1\n1.2.0\n1.10.0".into())
+    );
+    assert_eq!(
+        app_version(&["sort", "--reverse", "--json"], input),
+        (0, r#"["1.10.0","1.2.0","1.2.0-rc.1"]"#.into())
+    );
+    assert_eq!(app_version(&["max"], input), (0, "1.10.0".into()));
+    assert_eq!(app_version(&["max"], "").0, 65);
+    assert_eq!(app_version(&["sort"], "1.2.0\nnot a version\n").0, 65);
+}
+
+#[test]
+fn validate() {
+    assert_eq!(run(&["validate", "1.2.3"]), (0, "1.2.3".into()));
+    assert_eq!(run(&["validate", "--strict", "01.2.3"]).0, 1);
+    assert_eq!(
+        run(&["--json", "validate", "--lenient", "v1.2"]),
+        (
+            0,
+            r#"{"valid":true,"version":"1.2.0","coercions":["removed the 'v' prefix","the missing patch version defaulted to 0"]}"#
+                .into()
+        )
+    );
+    assert_eq!(
+        run(&["--json", "validate", "1.2"]),
+        (
+            1,
+            r#"{"valid":false,"error":"Invalid version format: expected 3 components, found 2"}"#
+                .into()
+        )
+    );
+    assert_eq!(run(&["validate", "--strict", "--lenient", "1.2.3"]).0, 64);
+}
+
+#[test]
+fn compatible() {
+    assert_eq!(run(&["compatible", "1.2.0", "1.9.0"]), (0, "true".into()));
+    assert_eq!(
+        run(&["compatible", "--policy", "cargo-caret", "0.2.0", "0.3.0"]),
+        (1, "false".into())
+    );
+    assert_eq!(
+        run(&[
+            "--json",
+            "compatible",
+            "--policy=same-minor",
+            "1.2.0",
+            "1.2.9"
+        ]),
+        (0, r#"{"compatible":true}"#.into())
+    );
+    assert_eq!(
+        run(&["compatible", "--policy", "at-least=1.3.0", "1.3.0", "2.0.0"]),
+        (0, "true".into())
+    );
+    assert_eq!(
+        run(&["compatible", "--policy=at-least=1.3.0", "1.2.9", "2.0.0"]),
+        (1, "false".into())
+    );
+    assert_eq!(
+        run(&["compatible", "--policy", "at-least=1.3", "1.3.0", "1.3.0"]).0,
+        64
+    );
+    assert_eq!(
+        run(&["compatible", "--policy", "loose", "1.0.0", "1.0.0"]).0,
+        64
+    );
+}
+
+#[test]
+fn manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Cargo.toml");
+    std::fs::write(
+        &path,
+        "[package]\nname = \"x\"\nversion = \"1.2.3\" # keep\n",
+    )
+    .unwrap();
+    let path = path.to_str().unwrap();
+
+    assert_eq!(run(&["manifest", "get", path]), (0, "1.2.3".into()));
+    assert_eq!(
+        run(&["manifest", "bump", "minor", path]),
+        (0, format!("1.2.3 -> 1.3.0 in {path}"))
+    );
+    assert_eq!(
+        run(&["--json", "manifest", "set", "2.0.0-rc.1", path]),
+        (
+            0,
+            format!(r#"{{"path":"{path}","previous":"1.3.0","version":"2.0.0-rc.1"}}"#)
+        )
+    );
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        "[package]\nname = \"x\"\nversion = \"2.0.0-rc.1\" # keep\n"
+    );
+    assert_eq!(run(&["manifest", "get", "missing/Cargo.toml"]).0, 65);
+    assert_eq!(run(&["manifest", "frob"]).0, 64);
+}
+
+#[test]
+fn workspace_bump() {
+    let dir = tempfile::tempdir().unwrap();
+    let write = |relative: &str, contents: &str| {
+        let path = dir.path().join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    };
+    write("Cargo.toml", "[workspace]\nmembers = [\"a\", \"b\"]\n");
+    write(
+        "a/Cargo.toml",
+        "[package]\nname = \"a\"\nversion = \"0.1.0\"\n",
+    );
+    let b = "[package]\nname = \"b\"\nversion = \"1.0.0\"\n\n[dependencies]\na = { path = \"../a\", version = \"0.1\" }\n";
+    write("b/Cargo.toml", b);
+    let root = dir.path().join("Cargo.toml");
+    let root = root.to_str().unwrap();
+
+    let plan = "\
+Bump a 0.1.0 -> 0.2.0 (minor, requested)
+Bump b 1.0.0 -> 2.0.0 (major, depends on a)
+Update b/Cargo.toml [dependencies] a: \"0.1\" -> \"0.2.0\"";
+    let args = ["workspace", "bump", "minor", "a", "--manifest-path", root];
+    assert_eq!(run(&[&args[..], &["--dry-run"]].concat()), (0, plan.into()));
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("b/Cargo.toml")).unwrap(),
+        b
+    );
+
+    assert_eq!(run(&args), (0, format!("{plan}\napplied")));
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("b/Cargo.toml")).unwrap(),
+        b.replace("1.0.0", "2.0.0").replace("\"0.1\"", "\"0.2.0\"")
+    );
+    assert_eq!(
+        run(&["--json", "workspace", "bump", "patch", "b", "--manifest-path", root, "--dry-run"]),
+        (
+            0,
+            r#"{"bumps":[{"package":"b","previous":"2.0.0","version":"2.0.1","level":"patch","reason":"requested"}],"requirements":[],"applied":false}"#
+                .into()
+        )
+    );
+    assert_eq!(
+        run(&["workspace", "bump", "patch", "c", "--manifest-path", root]).0,
+        65
+    );
+    assert_eq!(run(&["workspace", "bump", "patch"]).0, 64);
+}
+
+#[test]
+fn workspace_bump_in_current_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let write = |relative: &str, contents: &str| {
+        let path = dir.path().join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    };
+    write("Cargo.toml", "[workspace]\nmembers = [\"*\"]\n");
+    write(
+        "a/Cargo.toml",
+        "[package]\nname = \"a\"\nversion = \"0.1.0\"\n",
+    );
+    write(
+        "b/Cargo.toml",
+        "[package]\nname = \"b\"\nversion = \"1.0.0\"\n\n[dependencies]\na = { path = \"../a\", version = \">=0.1, <0.2\" }\n",
+    );
+
+    let run_in_dir = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_app-version"))
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        (
+            output.status.code().unwrap(),
+            String::from_utf8(output.stdout)
+                .unwrap()
+                .trim_end()
+                .to_string(),
+        )
+    };
+    assert_eq!(
+        run_in_dir(&["workspace", "bump", "minor", "a", "--dry-run"]),
+        (
+            0,
+            "\
+Bump a 0.1.0 -> 0.2.0 (minor, requested)
+Bump b 1.0.0 -> 2.0.0 (major, depends on a)
+Edit b/Cargo.toml [dependencies] a by hand: \">=0.1, <0.2\" does not allow 0.2.0"
+                .into()
+        )
+    );
+    assert_eq!(
+        run_in_dir(&["--json", "workspace", "bump", "minor", "a"]),
+        (
+            0,
+            r#"{"bumps":[{"package":"a","previous":"0.1.0","version":"0.2.0","level":"minor","reason":"requested"},{"package":"b","previous":"1.0.0","version":"2.0.0","level":"major","reason":"depends on a"}],"requirements":[{"path":"./b/Cargo.toml","table":"dependencies","dependency":"a","previous":">=0.1, <0.2","version":"0.2.0","requirement":null}],"applied":true}"#
+                .into()
+        )
+    );
+    assert!(std::fs::read_to_string(dir.path().join("b/Cargo.toml"))
+        .unwrap()
+        .contains("version = \">=0.1, <0.2\""));
+}
+
+#[test]
+fn usage_errors() {
+    assert_eq!(run(&["frobnicate"]).0, 64);
+    assert_eq!(run(&["compare", "1.0.0"]).0, 64);
+    assert_eq!(run(&["max", "--verbose"]).0, 64);
+    assert_eq!(run(&["compare", "-v", "1.0.0"]).0, 64);
+    // A negative number is a bad value rather than an unknown option.
+    assert_eq!(run(&["compare", "-1", "1.0.0"]).0, 65);
+    assert_eq!(
+        run(&["--json", "compare", "1.0.0"]),
+        (64, r#"{"error":"expected 2 arguments, found 1"}"#.into())
+    );
+    assert_eq!(run(&["--help"]).0, 0);
+}
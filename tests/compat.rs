@@ -0,0 +1,132 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::{
+    AtLeast, CargoCaret, CompatibilityPolicy, Exact, ReadCompatibility, SameMajor, SameMinor,
+    Version,
+};
+
+fn v(s: &str) -> Version {
+    s.parse().unwrap()
+}
+
+fn compatible(policy: &dyn CompatibilityPolicy, a: &str, b: &str) -> bool {
+    v(a).is_compatible_with(&v(b), policy)
+}
+
+#[test]
+fn same_major() {
+    assert!(compatible(&SameMajor, "1.2.3", "1.9.0"));
+    assert!(compatible(&SameMajor, "0.1.0", "0.2.0"));
+    assert!(!compatible(&SameMajor, "1.2.3", "2.2.3"));
+}
+
+#[test]
+fn cargo_caret() {
+    assert!(compatible(&CargoCaret, "1.2.3", "1.9.0"));
+    assert!(!compatible(&CargoCaret, "1.2.3", "2.0.0"));
+    assert!(compatible(&CargoCaret, "0.1.0", "0.1.7"));
+    assert!(!compatible(&CargoCaret, "0.1.0", "0.2.0"));
+    assert!(compatible(&CargoCaret, "0.0.3", "0.0.3"));
+    assert!(!compatible(&CargoCaret, "0.0.3", "0.0.4"));
+}
+
+#[test]
+fn exact() {
+    assert!(compatible(&Exact, "1.2.3", "1.2.3+build.7"));
+    assert!(!compatible(&Exact, "1.2.3", "1.2.4"));
+    assert!(!compatible(&Exact, "1.2.3", "1.2.3-rc.1"));
+}
+
+#[test]
+fn same_minor() {
+    assert!(compatible(&SameMinor, "1.2.3", "1.2.9"));
+    assert!(!compatible(&SameMinor, "1.2.3", "1.3.3"));
+}
+
+#[test]
+fn at_least() {
+    let policy = AtLeast(Version::new(1, 3, 0));
+    assert!(compatible(&policy, "1.3.0", "2.0.0"));
+    assert!(!compatible(&policy, "1.3.0", "1.2.9"));
+    assert!(!compatible(&policy, "1.3.0-rc.1", "1.4.0"));
+}
+
+#[test]
+fn closure_policy() {
+    let lockstep = |a: &Version, b: &Version| a == b;
+    assert!(!compatible(&lockstep, "1.2.3", "1.2.3+build.7"));
+}
+
+#[test]
+fn default_policy_is_same_major() {
+    assert_eq!(
+        v("0.1.0").is_compatible(&v("0.2.0")),
+        compatible(&SameMajor, "0.1.0", "0.2.0")
+    );
+}
+
+#[test]
+fn newer_reader_reads_older_data() {
+    assert_eq!(
+        v("1.4.0").can_read_data_from(&v("1.2.0"), &SameMajor),
+        ReadCompatibility::Compatible
+    );
+    assert_eq!(
+        v("1.2.0").can_be_read_by(&v("1.4.0"), &SameMajor),
+        ReadCompatibility::Compatible
+    );
+}
+
+#[test]
+fn older_reader_is_too_old() {
+    assert_eq!(
+        v("1.2.0").can_read_data_from(&v("1.4.0"), &SameMinor),
+        ReadCompatibility::ReaderTooOld
+    );
+    assert_eq!(
+        v("1.4.0").can_be_read_by(&v("1.2.0"), &SameMinor),
+        ReadCompatibility::ReaderTooOld
+    );
+}
+
+#[test]
+fn policy_decides_newer_writer() {
+    assert!(v("1.2.0")
+        .can_read_data_from(&v("1.4.0"), &SameMajor)
+        .is_compatible());
+    let not_newer = |reader: &Version, writer: &Version| writer <= reader;
+    assert_eq!(
+        v("1.2.0").can_read_data_from(&v("1.4.0"), &not_newer),
+        ReadCompatibility::ReaderTooOld
+    );
+}
+
+#[test]
+fn side_below_minimum_is_too_old() {
+    let policy = AtLeast(v("1.3.0"));
+    assert_eq!(
+        v("1.2.0").can_read_data_from(&v("1.1.0"), &policy),
+        ReadCompatibility::ReaderTooOld
+    );
+    assert_eq!(
+        v("1.2.0").can_read_data_from(&v("1.5.0"), &policy),
+        ReadCompatibility::ReaderTooOld
+    );
+    assert_eq!(
+        v("1.5.0").can_read_data_from(&v("1.2.0"), &policy),
+        ReadCompatibility::WriterTooOld
+    );
+    assert!(v("1.3.0")
+        .can_read_data_from(&v("1.5.0"), &policy)
+        .is_compatible());
+}
+
+#[test]
+fn unsupported_writer_is_too_old() {
+    let result = v("2.0.0").can_read_data_from(&v("1.9.0"), &SameMajor);
+    assert_eq!(result, ReadCompatibility::WriterTooOld);
+    assert!(!result.is_compatible());
+    assert_eq!(result.to_string(), "Writer is too old");
+}
@@ -0,0 +1,92 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::conventional::{recommend, Commit};
+use app_version::{BumpLevel, Component, Version, VersionError};
+
+#[test]
+fn parse_header() {
+    let commit = Commit::parse("feat(parser)!: drop v1 syntax\n\nMore details.").unwrap();
+    assert_eq!(commit.kind(), "feat");
+    assert_eq!(commit.scope(), Some("parser"));
+    assert!(commit.is_breaking());
+    assert_eq!(commit.description(), "drop v1 syntax");
+
+    let commit = Commit::parse("fix: off by one").unwrap();
+    assert_eq!(commit.scope(), None);
+    assert!(!commit.is_breaking());
+}
+
+#[test]
+fn not_conventional() {
+    for message in [
+        "Merge branch 'main'",
+        "feat:missing space",
+        "feat: ",
+        "feat(): empty scope",
+        "feat(parser: unclosed scope",
+        "fix bug: in parser",
+        "",
+    ] {
+        assert_eq!(Commit::parse(message), None, "{message:?}");
+    }
+}
+
+#[test]
+fn bump_levels() {
+    let level = |message| Commit::parse(message).unwrap().bump_level();
+    assert_eq!(level("fix: a"), Some(BumpLevel::Patch));
+    assert_eq!(level("feat: a"), Some(BumpLevel::Minor));
+    assert_eq!(level("FEAT: a"), Some(BumpLevel::Minor));
+    assert_eq!(level("feat!: a"), Some(BumpLevel::Major));
+    assert_eq!(level("chore!: a"), Some(BumpLevel::Major));
+    assert_eq!(
+        level("refactor: a\n\nBREAKING CHANGE: removed Foo"),
+        Some(BumpLevel::Major)
+    );
+    assert_eq!(
+        level("fix: a\n\nBREAKING-CHANGE: removed Foo"),
+        Some(BumpLevel::Major)
+    );
+    assert_eq!(level("docs: a"), None);
+    assert_eq!(
+        level("fix: a\n\nbreaking change: lowercase"),
+        Some(BumpLevel::Patch)
+    );
+}
+
+#[test]
+fn recommends_highest_bump() {
+    let current = Version::new(1, 4, 2);
+    let recommendation = recommend(&current, ["fix: a", "feat!: b", "feat: c"])
+        .unwrap()
+        .unwrap();
+    assert_eq!(recommendation.level(), BumpLevel::Major);
+    assert_eq!(recommendation.version(), Version::new(2, 0, 0));
+
+    let recommendation = recommend(&current, ["fix: a", "Merge pull request #4"])
+        .unwrap()
+        .unwrap();
+    assert_eq!(recommendation.version(), Version::new(1, 4, 3));
+
+    assert_eq!(recommend(&current, ["docs: a", "chore: b"]), Ok(None));
+    assert_eq!(recommend(&current, []), Ok(None));
+}
+
+#[test]
+fn breaking_changes_before_1_0_bump_minor() {
+    let recommendation = recommend(&Version::new(0, 3, 1), ["feat!: new wire format"])
+        .unwrap()
+        .unwrap();
+    assert_eq!(recommendation.level(), BumpLevel::Minor);
+    assert_eq!(recommendation.version(), Version::new(0, 4, 0));
+}
+
+#[test]
+fn overflow() {
+    assert_eq!(
+        recommend(&Version::new(1, u16::MAX, 0), ["feat: a"]),
+        Err(VersionError::Overflow(Component::Minor))
+    );
+}
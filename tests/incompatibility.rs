@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::{
+    AtLeast, CargoCaret, Component, Incompatibility, Role, Side, SuggestedAction, Version,
+};
+
+fn v(s: &str) -> Version {
+    s.parse().unwrap()
+}
+
+#[test]
+fn compatible_versions() {
+    assert_eq!(
+        v("1.2.0").check_compatible(&v("1.9.0"), Role::Client),
+        Ok(())
+    );
+}
+
+#[test]
+fn newer_server() {
+    let err = v("1.4.0")
+        .check_compatible(&v("2.0.0"), Role::Client)
+        .unwrap_err();
+    assert_eq!(err.component(), Some(Component::Major));
+    assert_eq!(err.newer_side(), Some(Side::Remote));
+    assert_eq!(err.newer_role(), Some(Role::Server));
+    assert_eq!(err.suggested_action(), Some(SuggestedAction::UpgradeClient));
+}
+
+#[test]
+fn newer_client() {
+    let err = v("2.0.0")
+        .check_compatible(&v("1.4.0"), Role::Client)
+        .unwrap_err();
+    assert_eq!(err.newer_side(), Some(Side::Local));
+    assert_eq!(err.suggested_action(), Some(SuggestedAction::UpgradeServer));
+    assert_eq!(
+        err.to_string(),
+        "The client has a newer major version than the server. Please upgrade the server."
+    );
+}
+
+#[test]
+fn names_first_differing_component() {
+    let err = v("0.2.0")
+        .check_compatible_with(&v("0.3.1"), Role::Server, &CargoCaret)
+        .unwrap_err();
+    assert_eq!(err.component(), Some(Component::Minor));
+    assert_eq!(err.suggested_action(), Some(SuggestedAction::UpgradeServer));
+
+    let result = v("0.0.3").check_compatible_with(&v("0.0.3-rc.1"), Role::Server, &CargoCaret);
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn equal_versions_rejected_by_policy() {
+    let policy = AtLeast(v("2.0.0"));
+    let err = v("1.0.0")
+        .check_compatible_with(&v("1.0.0"), Role::Client, &policy)
+        .unwrap_err();
+    assert_eq!(err.component(), None);
+    assert_eq!(err.suggested_action(), None);
+    assert_eq!(
+        err.to_string(),
+        "The client and server versions are not compatible."
+    );
+}
+
+#[test]
+fn wire_code_round_trip() {
+    let err = v("1.4.0")
+        .check_compatible(&v("2.0.0"), Role::Client)
+        .unwrap_err();
+    assert_eq!(err.code(), 0b0_1001);
+    assert_eq!(Incompatibility::from_code(err.code()), Some(err));
+
+    // The server receives the client's view and reverses it.
+    let seen_by_server = Incompatibility::from_code(err.code()).unwrap().reversed();
+    assert_eq!(seen_by_server.local_role(), Role::Server);
+    assert_eq!(seen_by_server.newer_side(), Some(Side::Local));
+    assert_eq!(
+        seen_by_server.suggested_action(),
+        Some(SuggestedAction::UpgradeClient)
+    );
+}
+
+#[test]
+fn invalid_wire_codes() {
+    for code in [0b0_0101, 0b0_1000, 0b10_0000] {
+        assert_eq!(Incompatibility::from_code(code), None, "{code:#b}");
+    }
+}
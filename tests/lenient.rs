@@ -0,0 +1,137 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::{Coercion, Component, LenientOptions, NumberErrorKind, Version, VersionError};
+use std::str::FromStr;
+
+fn lenient(s: &str) -> Result<(Version, Vec<Coercion>), VersionError> {
+    Version::parse_lenient(s, &LenientOptions::default())
+        .map(|parsed| (parsed.version(), parsed.coercions().to_vec()))
+}
+
+#[test]
+fn exact_input_has_no_coercions() {
+    let parsed = Version::parse_lenient("1.2.3-rc.1+b7", &LenientOptions::default()).unwrap();
+    assert!(parsed.is_exact());
+    assert_eq!(
+        parsed.version(),
+        Version::from_str("1.2.3-rc.1+b7").unwrap()
+    );
+}
+
+#[test]
+fn user_entered_versions() {
+    assert_eq!(
+        lenient("v1.2").unwrap(),
+        (
+            Version::new(1, 2, 0),
+            vec![
+                Coercion::StrippedPrefix('v'),
+                Coercion::Defaulted(Component::Patch)
+            ]
+        )
+    );
+    assert_eq!(
+        lenient("V1.2.3 ").unwrap(),
+        (
+            Version::new(1, 2, 3),
+            vec![Coercion::Trimmed, Coercion::StrippedPrefix('V')]
+        )
+    );
+    assert_eq!(
+        lenient("1").unwrap(),
+        (
+            Version::new(1, 0, 0),
+            vec![
+                Coercion::Defaulted(Component::Minor),
+                Coercion::Defaulted(Component::Patch)
+            ]
+        )
+    );
+
+    let (version, coercions) = lenient("1.2.3-final").unwrap();
+    assert_eq!(version.prerelease().as_str(), "final");
+    assert!(coercions.is_empty());
+
+    let (version, _) = lenient("v2.1-beta.2+exp").unwrap();
+    assert_eq!(version.to_string(), "2.1.0-beta.2+exp");
+}
+
+#[test]
+fn from_str_stays_strict() {
+    for s in ["v1.2", "V1.2.3 ", "1"] {
+        assert!(Version::from_str(s).is_err(), "{s}");
+    }
+}
+
+#[test]
+fn trailing_characters() {
+    assert_eq!(
+        lenient(" v1.2.3 (beta)"),
+        Err(VersionError::TrailingCharacters {
+            offset: 7,
+            text: " (beta)".to_string()
+        })
+    );
+
+    let options = LenientOptions {
+        allow_trailing: true,
+        ..LenientOptions::default()
+    };
+    let parsed = Version::parse_lenient("1.2.3.4", &options).unwrap();
+    assert_eq!(parsed.version(), Version::new(1, 2, 3));
+    assert_eq!(
+        parsed.coercions(),
+        [Coercion::DiscardedTrailing(".4".to_string())]
+    );
+    assert_eq!(parsed.coercions()[0].to_string(), "ignored trailing \".4\"");
+}
+
+#[test]
+fn disabled_coercions() {
+    let options = LenientOptions {
+        trim: false,
+        allow_v_prefix: false,
+        fill_missing: false,
+        allow_trailing: false,
+    };
+    assert_eq!(
+        Version::parse_lenient("1.2", &options),
+        Err(VersionError::InvalidComponentCount {
+            expected: 3,
+            found: 2
+        })
+    );
+    assert_eq!(
+        Version::parse_lenient("v1.2.3", &options),
+        Err(VersionError::InvalidNumber {
+            component: Component::Major,
+            offset: 0,
+            text: "v1".to_string(),
+            kind: NumberErrorKind::InvalidDigit,
+        })
+    );
+}
+
+#[test]
+fn numbers_are_still_checked() {
+    assert_eq!(
+        lenient("v1.70000"),
+        Err(VersionError::InvalidNumber {
+            component: Component::Minor,
+            offset: 3,
+            text: "70000".to_string(),
+            kind: NumberErrorKind::Overflow,
+        })
+    );
+    assert_eq!(
+        lenient(""),
+        Err(VersionError::InvalidNumber {
+            component: Component::Major,
+            offset: 0,
+            text: String::new(),
+            kind: NumberErrorKind::Empty,
+        })
+    );
+}
@@ -0,0 +1,161 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+#![cfg(feature = "manifest")]
+
+use app_version::manifest::{self, Manifest, ManifestError};
+use app_version::{BumpLevel, Version};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PACKAGE: &str = r#"# The game server.
+[package]
+name = "nimble-server"
+version   =   "0.4.2"   # bumped by the release script
+edition = "2021"
+
+[dependencies]
+app-version = "0.0.2"
+"#;
+
+fn write(dir: &Path, relative: &str, contents: &str) -> PathBuf {
+    let path = dir.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn read_package_version() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "Cargo.toml", PACKAGE);
+    let manifest = Manifest::open(&path).unwrap();
+    assert_eq!(manifest.name(), Some("nimble-server"));
+    assert_eq!(manifest.version().unwrap(), Version::new(0, 4, 2));
+    assert_eq!(
+        manifest::read_version(&path).unwrap(),
+        Version::new(0, 4, 2)
+    );
+}
+
+#[test]
+fn bump_preserves_formatting() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "Cargo.toml", PACKAGE);
+    let change = manifest::bump(&path, BumpLevel::Minor).unwrap();
+    assert_eq!(change.previous, Version::new(0, 4, 2));
+    assert_eq!(change.version, Version::new(0, 5, 0));
+    assert_eq!(change.path, path);
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        PACKAGE.replace("\"0.4.2\"", "\"0.5.0\"")
+    );
+}
+
+#[test]
+fn inherited_version() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = write(
+        dir.path(),
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.2.3\" # shared\n",
+    );
+    let member = write(
+        dir.path(),
+        "crates/client/Cargo.toml",
+        "[package]\nname = \"client\"\nversion.workspace = true\n",
+    );
+
+    let manifest = Manifest::open(&member).unwrap();
+    assert!(manifest.is_version_inherited());
+    assert!(matches!(
+        manifest.version(),
+        Err(ManifestError::InheritedVersion)
+    ));
+    assert_eq!(
+        manifest::read_version(&member).unwrap(),
+        Version::new(1, 2, 3)
+    );
+
+    let change = manifest::bump(&member, BumpLevel::Patch).unwrap();
+    assert_eq!(change.version, Version::new(1, 2, 4));
+    assert_eq!(change.path, fs::canonicalize(&root).unwrap());
+    assert!(fs::read_to_string(&root)
+        .unwrap()
+        .contains("version = \"1.2.4\" # shared"));
+    assert_eq!(
+        fs::read_to_string(&member).unwrap(),
+        "[package]\nname = \"client\"\nversion.workspace = true\n"
+    );
+}
+
+#[test]
+fn explicit_workspace_path() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "root/Cargo.toml",
+        "[workspace]\n\n[workspace.package]\nversion = \"3.0.0\"\n",
+    );
+    let member = write(
+        dir.path(),
+        "elsewhere/Cargo.toml",
+        "[package]\nname = \"x\"\nworkspace = \"../root\"\nversion = { workspace = true }\n",
+    );
+    assert_eq!(
+        manifest::read_version(&member).unwrap(),
+        Version::new(3, 0, 0)
+    );
+}
+
+#[test]
+fn errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let orphan = write(
+        dir.path(),
+        "Cargo.toml",
+        "[package]\nname = \"orphan\"\nversion.workspace = true\n",
+    );
+    assert!(matches!(
+        manifest::read_version(&orphan),
+        Err(ManifestError::WorkspaceNotFound)
+    ));
+
+    let missing = write(dir.path(), "a/Cargo.toml", "[package]\nname = \"a\"\n");
+    assert!(matches!(
+        manifest::read_version(&missing),
+        Err(ManifestError::MissingVersion)
+    ));
+
+    let invalid = write(dir.path(), "b/Cargo.toml", "[package]\nversion = \"1.2\"\n");
+    assert!(matches!(
+        manifest::read_version(&invalid),
+        Err(ManifestError::Version(_))
+    ));
+
+    let broken = write(dir.path(), "c/Cargo.toml", "[package\n");
+    assert!(matches!(
+        manifest::read_version(&broken),
+        Err(ManifestError::Toml(_))
+    ));
+
+    assert!(matches!(
+        manifest::read_version(dir.path().join("none/Cargo.toml")),
+        Err(ManifestError::Io(_))
+    ));
+
+    let max = write(
+        dir.path(),
+        "d/Cargo.toml",
+        "[package]\nversion = \"65535.0.0\"\n",
+    );
+    assert!(matches!(
+        manifest::bump(&max, BumpLevel::Major),
+        Err(ManifestError::Version(_))
+    ));
+    assert_eq!(
+        fs::read_to_string(&max).unwrap(),
+        "[package]\nversion = \"65535.0.0\"\n"
+    );
+}
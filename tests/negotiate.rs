@@ -0,0 +1,155 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::negotiate::{negotiate, Answer, Offer, Rejection};
+use app_version::{Version, VersionReq};
+use std::io::Cursor;
+
+fn v(s: &str) -> Version {
+    s.parse().unwrap()
+}
+
+fn offer(versions: &[&str], ranges: &[&str]) -> Offer {
+    let offer = versions.iter().fold(Offer::new(), |offer, version| {
+        offer.with_version(v(version))
+    });
+    ranges.iter().fold(offer, |offer, range| {
+        offer.with_range(VersionReq::parse(range).unwrap())
+    })
+}
+
+#[test]
+fn picks_highest_common_version() {
+    let server = offer(&["1.0.0", "1.2.0", "2.0.0"], &[]);
+    let client = offer(&["1.2.0", "1.0.0", "1.3.0"], &[]);
+    assert_eq!(negotiate(&server, &client), Ok(v("1.2.0")));
+}
+
+#[test]
+fn ranges_accept_versions_of_the_other_side() {
+    let server = offer(&[], &[">=1.2, <2"]);
+    let client = offer(&["1.1.0", "1.4.2", "2.0.0"], &[]);
+    assert_eq!(negotiate(&server, &client), Ok(v("1.4.2")));
+
+    let server = offer(&["1.5.0", "1.9.0"], &["~1.5"]);
+    let client = offer(&[], &["^1.6"]);
+    assert_eq!(negotiate(&server, &client), Ok(v("1.9.0")));
+}
+
+#[test]
+fn result_does_not_depend_on_side() {
+    let a = offer(&["1.0.0", "1.2.0", "1.4.0"], &["^1"]);
+    let b = offer(&["1.3.0"], &[">=1.1, <1.4"]);
+    assert_eq!(negotiate(&a, &b), negotiate(&b, &a));
+    assert_eq!(negotiate(&a, &b), Ok(v("1.3.0")));
+}
+
+#[test]
+fn tie_break_on_build_metadata_is_deterministic() {
+    let a = offer(&["1.2.0+build.1"], &[]);
+    let b = offer(&["1.2.0+build.2"], &[]);
+    assert_eq!(negotiate(&a, &b), Ok(v("1.2.0+build.2")));
+    assert_eq!(negotiate(&b, &a), Ok(v("1.2.0+build.2")));
+}
+
+#[test]
+fn empty_offers_are_rejected() {
+    let some = offer(&["1.0.0"], &[]);
+    assert_eq!(
+        negotiate(&some, &Offer::new()),
+        Err(Rejection::ClientOfferEmpty)
+    );
+    assert_eq!(
+        negotiate(&Offer::new(), &some),
+        Err(Rejection::ServerOfferEmpty)
+    );
+}
+
+#[test]
+fn empty_intersection_is_rejected() {
+    let server = offer(&["2.0.0", "2.1.0"], &[]);
+    let client = offer(&["1.4.0"], &[]);
+    let rejection = negotiate(&server, &client).unwrap_err();
+    assert_eq!(
+        rejection,
+        Rejection::NoCommonVersion {
+            server_highest: Some(v("2.1.0"))
+        }
+    );
+    assert_eq!(
+        rejection.to_string(),
+        "No version is supported by both client and server (server supports up to 2.1.0)"
+    );
+
+    let server = offer(&[], &["^1"]);
+    let client = offer(&[], &["^2"]);
+    assert_eq!(
+        negotiate(&server, &client),
+        Err(Rejection::NoCommonVersion {
+            server_highest: None
+        })
+    );
+}
+
+#[test]
+fn ranges_are_intersected() {
+    // Without a named version, the lowest release both ranges allow is chosen.
+    let server = offer(&[], &["^1"]);
+    let client = offer(&[], &["^1"]);
+    assert_eq!(negotiate(&server, &client), Ok(v("1.0.0")));
+
+    let server = offer(&[], &[">=1.2, <2"]);
+    let client = offer(&[], &["^1.4", "^3"]);
+    assert_eq!(negotiate(&server, &client), Ok(v("1.4.0")));
+    assert_eq!(negotiate(&client, &server), Ok(v("1.4.0")));
+
+    // An inclusive upper bound is the highest version in its interval.
+    let server = offer(&[], &[">=1.0.0, <=1.7.2"]);
+    let client = offer(&[], &[">1.5"]);
+    assert_eq!(negotiate(&server, &client), Ok(v("1.7.2")));
+
+    // A named version that both support is still preferred.
+    let server = offer(&["1.3.0"], &["^1"]);
+    let client = offer(&[], &["^1"]);
+    assert_eq!(negotiate(&server, &client), Ok(v("1.3.0")));
+}
+
+#[test]
+fn offer_wire_round_trip() {
+    let original = offer(
+        &["1.2.3", "2.0.0-rc.1+sha.1"],
+        &[">=1.2, <2", "~2.0.0-rc.1"],
+    );
+    let mut buf = Vec::new();
+    original.write_to(&mut buf).unwrap();
+    assert_eq!(Offer::read_from(&mut Cursor::new(buf)).unwrap(), original);
+}
+
+#[test]
+fn answer_wire_round_trip() {
+    for answer in [
+        Answer::Accepted(v("1.2.3")),
+        Answer::Rejected(Rejection::ClientOfferEmpty),
+        Answer::Rejected(Rejection::ServerOfferEmpty),
+        Answer::Rejected(Rejection::NoCommonVersion {
+            server_highest: Some(v("3.0.0")),
+        }),
+        Answer::Rejected(Rejection::NoCommonVersion {
+            server_highest: None,
+        }),
+    ] {
+        let mut buf = Vec::new();
+        answer.write_to(&mut buf).unwrap();
+        assert_eq!(Answer::read_from(&mut Cursor::new(buf)).unwrap(), answer);
+    }
+}
+
+#[test]
+fn answer_wire_layout() {
+    let mut buf = Vec::new();
+    Answer::Accepted(v("0.1.2")).write_to(&mut buf).unwrap();
+    assert_eq!(buf, [0, 3, 0, 1, 8]);
+
+    assert!(Answer::read_from(&mut Cursor::new([9])).is_err());
+}
@@ -0,0 +1,313 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::npm::Range;
+use app_version::Version;
+
+fn range(s: &str) -> Range {
+    Range::parse(s).unwrap_or_else(|err| panic!("{s}: {err}"))
+}
+
+fn v(s: &str) -> Version {
+    s.parse().unwrap()
+}
+
+/// Cases from node-semver `test/fixtures/range-include.js` that need neither
+/// loose parsing nor `includePrerelease`.
+const INCLUDE: &[(&str, &str)] = &[
+    ("1.0.0 - 2.0.0", "1.2.3"),
+    ("^1.2.3+build", "1.2.3"),
+    ("^1.2.3+build", "1.3.0"),
+    ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3"),
+    ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3-pre.2"),
+    ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "2.4.3-alpha"),
+    ("1.2.3+asdf - 2.4.3+asdf", "1.2.3"),
+    ("1.0.0", "1.0.0"),
+    (">=*", "0.2.4"),
+    ("", "1.0.0"),
+    ("*", "1.2.3"),
+    (">=1.0.0", "1.0.0"),
+    (">=1.0.0", "1.0.1"),
+    (">=1.0.0", "1.1.0"),
+    (">1.0.0", "1.0.1"),
+    (">1.0.0", "1.1.0"),
+    ("<=2.0.0", "2.0.0"),
+    ("<=2.0.0", "1.9999.9999"),
+    ("<=2.0.0", "0.2.9"),
+    ("<2.0.0", "1.9999.9999"),
+    ("<2.0.0", "0.2.9"),
+    (">= 1.0.0", "1.0.0"),
+    (">=  1.0.0", "1.0.1"),
+    (">=   1.0.0", "1.1.0"),
+    ("> 1.0.0", "1.0.1"),
+    (">  1.0.0", "1.1.0"),
+    ("<=   2.0.0", "2.0.0"),
+    ("<= 2.0.0", "1.9999.9999"),
+    ("<=  2.0.0", "0.2.9"),
+    ("<    2.0.0", "1.9999.9999"),
+    ("<\t2.0.0", "0.2.9"),
+    (">=0.1.97", "0.1.97"),
+    ("0.1.20 || 1.2.4", "1.2.4"),
+    (">=0.2.3 || <0.0.1", "0.0.0"),
+    (">=0.2.3 || <0.0.1", "0.2.3"),
+    (">=0.2.3 || <0.0.1", "0.2.4"),
+    ("||", "1.3.4"),
+    ("2.x.x", "2.1.3"),
+    ("1.2.x", "1.2.3"),
+    ("1.2.x || 2.x", "2.1.3"),
+    ("1.2.x || 2.x", "1.2.3"),
+    ("x", "1.2.3"),
+    ("2.*.*", "2.1.3"),
+    ("1.2.*", "1.2.3"),
+    ("1.2.* || 2.*", "2.1.3"),
+    ("1.2.* || 2.*", "1.2.3"),
+    ("2", "2.1.2"),
+    ("2.3", "2.3.1"),
+    ("~0.0.1", "0.0.1"),
+    ("~0.0.1", "0.0.2"),
+    ("~x", "0.0.9"),
+    ("~2", "2.0.9"),
+    ("~2.4", "2.4.0"),
+    ("~2.4", "2.4.5"),
+    ("~>3.2.1", "3.2.2"),
+    ("~1", "1.2.3"),
+    ("~>1", "1.2.3"),
+    ("~> 1", "1.2.3"),
+    ("~1.0", "1.0.2"),
+    ("~ 1.0", "1.0.2"),
+    ("~ 1.0.3", "1.0.12"),
+    (">=1", "1.0.0"),
+    (">= 1", "1.0.0"),
+    ("<1.2", "1.1.1"),
+    ("< 1.2", "1.1.1"),
+    ("~v0.5.4-pre", "0.5.5"),
+    ("~v0.5.4-pre", "0.5.4"),
+    ("=0.7.x", "0.7.2"),
+    ("<=0.7.x", "0.7.2"),
+    (">=0.7.x", "0.7.2"),
+    ("<=0.7.x", "0.6.2"),
+    ("~1.2.1 >=1.2.3", "1.2.3"),
+    ("~1.2.1 =1.2.3", "1.2.3"),
+    ("~1.2.1 1.2.3", "1.2.3"),
+    ("~1.2.1 >=1.2.3 1.2.3", "1.2.3"),
+    ("~1.2.1 1.2.3 >=1.2.3", "1.2.3"),
+    (">=1.2.1 1.2.3", "1.2.3"),
+    ("1.2.3 >=1.2.1", "1.2.3"),
+    (">=1.2.3 >=1.2.1", "1.2.3"),
+    (">=1.2.1 >=1.2.3", "1.2.3"),
+    (">=1.2", "1.2.8"),
+    ("^1.2.3", "1.8.1"),
+    ("^0.1.2", "0.1.2"),
+    ("^0.1", "0.1.2"),
+    ("^0.0.1", "0.0.1"),
+    ("^1.2", "1.4.2"),
+    ("^1.2 ^1", "1.4.2"),
+    ("^1.2.3-alpha", "1.2.3-pre"),
+    ("^1.2.0-alpha", "1.2.0-pre"),
+    ("^0.0.1-alpha", "0.0.1-beta"),
+    ("^0.0.1-alpha", "0.0.1"),
+    ("^0.1.1-alpha", "0.1.1-beta"),
+    ("^x", "1.2.3"),
+    ("x - 1.0.0", "0.9.7"),
+    ("x - 1.x", "0.9.7"),
+    ("1.0.0 - x", "1.9.7"),
+    ("1.x - x", "1.9.7"),
+    ("<=7.x", "7.9.9"),
+];
+
+/// Cases from node-semver `test/fixtures/range-exclude.js` that need neither
+/// loose parsing nor `includePrerelease`.
+const EXCLUDE: &[(&str, &str)] = &[
+    ("1.0.0 - 2.0.0", "2.2.3"),
+    ("1.2.3+asdf - 2.4.3+asdf", "1.2.3-pre.2"),
+    ("1.2.3+asdf - 2.4.3+asdf", "2.4.3-alpha"),
+    ("^1.2.3+build", "2.0.0"),
+    ("^1.2.3+build", "1.2.0"),
+    ("^1.2.3", "1.2.3-pre"),
+    ("^1.2", "1.2.0-pre"),
+    (">1.2", "1.3.0-beta"),
+    ("<=1.2.3", "1.2.3-beta"),
+    ("^1.2.3", "1.2.3-beta"),
+    ("=0.7.x", "0.7.0-asdf"),
+    (">=0.7.x", "0.7.0-asdf"),
+    ("<=0.7.x", "0.7.0-asdf"),
+    ("1.0.0", "1.0.1"),
+    (">=1.0.0", "0.0.0"),
+    (">=1.0.0", "0.0.1"),
+    (">=1.0.0", "0.1.0"),
+    (">1.0.0", "0.0.1"),
+    (">1.0.0", "0.1.0"),
+    ("<=2.0.0", "3.0.0"),
+    ("<=2.0.0", "2.9999.9999"),
+    ("<=2.0.0", "2.2.9"),
+    ("<2.0.0", "2.9999.9999"),
+    ("<2.0.0", "2.2.9"),
+    (">=0.1.97", "0.1.93"),
+    ("0.1.20 || 1.2.4", "1.2.3"),
+    (">=0.2.3 || <0.0.1", "0.0.3"),
+    (">=0.2.3 || <0.0.1", "0.2.2"),
+    ("2.x.x", "1.1.3"),
+    ("2.x.x", "3.1.3"),
+    ("1.2.x", "1.3.3"),
+    ("1.2.x || 2.x", "3.1.3"),
+    ("1.2.x || 2.x", "1.1.3"),
+    ("2.*.*", "1.1.3"),
+    ("2.*.*", "3.1.3"),
+    ("1.2.*", "1.3.3"),
+    ("1.2.* || 2.*", "3.1.3"),
+    ("1.2.* || 2.*", "1.1.3"),
+    ("2", "1.1.2"),
+    ("2.3", "2.4.1"),
+    ("~0.0.1", "0.1.0-alpha"),
+    ("~0.0.1", "0.1.0"),
+    ("~2.4", "2.5.0"),
+    ("~2.4", "2.3.9"),
+    ("~>3.2.1", "3.3.2"),
+    ("~>3.2.1", "3.2.0"),
+    ("~1", "0.2.3"),
+    ("~>1", "2.2.3"),
+    ("~1.0", "1.1.0"),
+    ("<1", "1.0.0"),
+    (">=1.2", "1.1.1"),
+    ("~v0.5.4-beta", "0.5.4-alpha"),
+    ("=0.7.x", "0.8.2"),
+    (">=0.7.x", "0.6.2"),
+    ("<0.7.x", "0.7.2"),
+    ("<1.2.3", "1.2.3-beta"),
+    ("=1.2.3", "1.2.3-beta"),
+    (">1.2", "1.2.8"),
+    ("^0.0.1", "0.0.2-alpha"),
+    ("^0.0.1", "0.0.2"),
+    ("^1.2.3", "2.0.0-alpha"),
+    ("^1.2.3", "1.2.2"),
+    ("^1.2", "1.1.9"),
+    ("^1.0.0", "2.0.0-rc1"),
+    ("^1.2.3-rc2", "2.0.0"),
+    ("1 - 2", "3.0.0-pre"),
+    ("1 - 2", "2.0.0-pre"),
+    ("1 - 2", "1.0.0-pre"),
+    ("1.0 - 2", "1.0.0-pre"),
+    ("1.1.x", "1.0.0-a"),
+    ("1.1.x", "1.1.0-a"),
+    ("1.1.x", "1.2.0-a"),
+    ("1.x", "1.0.0-a"),
+    ("1.x", "1.1.0-a"),
+    ("1.x", "1.2.0-a"),
+    (">=1.0.0 <1.1.0", "1.1.0"),
+    (">=1.0.0 <1.1.0", "1.1.0-pre"),
+    (">=1.0.0 <1.1.0-pre", "1.1.0-pre"),
+];
+
+#[test]
+fn node_semver_range_include() {
+    for (r, version) in INCLUDE {
+        assert!(
+            range(r).matches(&v(version)),
+            "{r:?} should include {version}"
+        );
+    }
+}
+
+#[test]
+fn node_semver_range_exclude() {
+    for (r, version) in EXCLUDE {
+        assert!(
+            !range(r).matches(&v(version)),
+            "{r:?} should exclude {version}"
+        );
+    }
+}
+
+/// Desugaring cases from node-semver `test/fixtures/range-parse.js`.
+#[test]
+fn desugaring() {
+    for (input, desugared) in [
+        ("1.0.0 - 2.0.0", ">=1.0.0 <=2.0.0"),
+        ("1 - 2", ">=1.0.0 <3.0.0-0"),
+        ("1.0 - 2.0", ">=1.0.0 <2.1.0-0"),
+        ("1.0.0", "1.0.0"),
+        (">=*", "*"),
+        ("", "*"),
+        ("*", "*"),
+        (">1.0.0", ">1.0.0"),
+        ("0.1.20 || 1.2.4", "0.1.20 || 1.2.4"),
+        (">=0.2.3 || <0.0.1", ">=0.2.3 || <0.0.1"),
+        ("||", "* || *"),
+        ("2.x.x", ">=2.0.0 <3.0.0-0"),
+        ("1.2.x", ">=1.2.0 <1.3.0-0"),
+        ("1.2.x || 2.x", ">=1.2.0 <1.3.0-0 || >=2.0.0 <3.0.0-0"),
+        ("2.3", ">=2.3.0 <2.4.0-0"),
+        ("~2.4", ">=2.4.0 <2.5.0-0"),
+        ("~>3.2.1", ">=3.2.1 <3.3.0-0"),
+        ("~1", ">=1.0.0 <2.0.0-0"),
+        ("~> 1", ">=1.0.0 <2.0.0-0"),
+        ("~1.0", ">=1.0.0 <1.1.0-0"),
+        ("^0", ">=0.0.0 <1.0.0-0"),
+        ("^ 1", ">=1.0.0 <2.0.0-0"),
+        ("^0.1", ">=0.1.0 <0.2.0-0"),
+        ("^1.0", ">=1.0.0 <2.0.0-0"),
+        ("^1.2", ">=1.2.0 <2.0.0-0"),
+        ("^0.0.1", ">=0.0.1 <0.0.2-0"),
+        ("^0.0.1-beta", ">=0.0.1-beta <0.0.2-0"),
+        ("^0.1.2", ">=0.1.2 <0.2.0-0"),
+        ("^1.2.3", ">=1.2.3 <2.0.0-0"),
+        ("^1.2.3-beta.4", ">=1.2.3-beta.4 <2.0.0-0"),
+        ("<1", "<1.0.0-0"),
+        ("< 1", "<1.0.0-0"),
+        (">=1", ">=1.0.0"),
+        (">= 1", ">=1.0.0"),
+        ("<1.2", "<1.2.0-0"),
+        ("< 1.2", "<1.2.0-0"),
+        (">1", ">=2.0.0"),
+        (">1.2", ">=1.3.0"),
+        ("<=1.2", "<1.3.0-0"),
+        ("~v0.5.4-pre", ">=0.5.4-pre <0.6.0-0"),
+        ("=0.7.x", ">=0.7.0 <0.8.0-0"),
+        ("<=0.7.x", "<0.8.0-0"),
+        (">=0.7.x", ">=0.7.0"),
+        ("<0.7.x", "<0.7.0-0"),
+        ("^0.0.x", ">=0.0.0 <0.1.0-0"),
+        ("^0.x", ">=0.0.0 <1.0.0-0"),
+        (">*", "<0.0.0-0"),
+        ("<*", "<0.0.0-0"),
+    ] {
+        assert_eq!(range(input).to_string(), desugared, "{input:?}");
+    }
+}
+
+#[test]
+fn upper_bound_overflow_moves_to_next_component() {
+    assert_eq!(range("<=1.65535").to_string(), "<2.0.0-0");
+    assert_eq!(range("~1.65535.0").to_string(), ">=1.65535.0 <2.0.0-0");
+    assert_eq!(range("1.65535.x").to_string(), ">=1.65535.0 <2.0.0-0");
+    assert_eq!(range("^0.0.65535").to_string(), ">=0.0.65535 <0.1.0-0");
+    assert_eq!(range("1.0 - 1.65535").to_string(), ">=1.0.0 <2.0.0-0");
+    assert_eq!(range(">1.65535").to_string(), ">=2.0.0");
+    assert!(!range("~1.65535.0").matches(&v("9.0.0")));
+    assert!(!range("^0.0.65535").matches(&v("4.0.0")));
+    assert!(range("1.65535.x").matches(&v("1.65535.65535")));
+
+    // Only an overflow of the major leaves the bound out.
+    assert_eq!(range("^65535.1.0").to_string(), ">=65535.1.0");
+    assert!(range("~65535").matches(&v("65535.65535.65535")));
+}
+
+#[test]
+fn invalid() {
+    for invalid in [
+        ">=",
+        "1.2.3.4",
+        "a.b.c",
+        "1.2.3-",
+        ">= >= 1.0.0",
+        "1.2.3 -",
+        "1.2-rc",
+        "^1-rc.1",
+        "vv1.2.3",
+        "==1.2.3",
+        "v=1.2.3",
+    ] {
+        assert!(Range::parse(invalid).is_err(), "{invalid:?}");
+    }
+}
@@ -0,0 +1,118 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::{Op, Version, VersionReq};
+
+fn req(s: &str) -> VersionReq {
+    s.parse().unwrap()
+}
+
+fn v(s: &str) -> Version {
+    s.parse().unwrap()
+}
+
+fn assert_matches(requirement: &str, matching: &[&str], not_matching: &[&str]) {
+    let parsed = req(requirement);
+    for version in matching {
+        assert!(
+            parsed.matches(&v(version)),
+            "{requirement} should match {version}"
+        );
+    }
+    for version in not_matching {
+        assert!(
+            !parsed.matches(&v(version)),
+            "{requirement} should not match {version}"
+        );
+    }
+}
+
+#[test]
+fn caret() {
+    assert_matches("^1.3", &["1.3.0", "1.9.9"], &["1.2.9", "2.0.0"]);
+    assert_matches("1.3", &["1.3.0", "1.9.9"], &["1.2.9", "2.0.0"]);
+    assert_matches("^0.2.3", &["0.2.3", "0.2.9"], &["0.2.2", "0.3.0"]);
+    assert_matches("^0.0.3", &["0.0.3"], &["0.0.4", "0.1.0"]);
+    assert_matches("^0.0", &["0.0.0", "0.0.9"], &["0.1.0"]);
+}
+
+#[test]
+fn tilde() {
+    assert_matches("~1.3.2", &["1.3.2", "1.3.9"], &["1.3.1", "1.4.0"]);
+    assert_matches("~1.3", &["1.3.0", "1.3.9"], &["1.4.0"]);
+    assert_matches("~1", &["1.0.0", "1.9.0"], &["2.0.0", "0.9.0"]);
+}
+
+#[test]
+fn comparison_ranges() {
+    assert_matches(">=1.2, <2", &["1.2.0", "1.99.0"], &["1.1.9", "2.0.0"]);
+    assert_matches(">1.2.3", &["1.2.4", "2.0.0"], &["1.2.3"]);
+    assert_matches("<=1.2", &["1.2.9", "0.1.0"], &["1.3.0"]);
+    assert_matches("=1.2.3", &["1.2.3", "1.2.3+build.1"], &["1.2.4"]);
+    assert_matches("=1.2.3+build.1", &["1.2.3", "1.2.3+build.2"], &["1.2.4"]);
+}
+
+#[test]
+fn wildcards() {
+    assert_matches("1.*", &["1.0.0", "1.9.9"], &["2.0.0", "0.9.0"]);
+    assert_matches("1.2.x", &["1.2.0", "1.2.9"], &["1.3.0"]);
+    assert_matches("*", &["0.0.1", "9.9.9"], &["1.0.0-rc.1"]);
+    assert_matches("*, <2", &["0.0.1", "1.9.9"], &["2.0.0", "1.0.0-rc.1"]);
+    assert_eq!(req("1.*").comparators()[0].op(), Op::Wildcard);
+}
+
+#[test]
+fn prerelease_requires_opt_in() {
+    assert_matches(">=1.2.0", &["1.3.0"], &["1.3.0-rc.1"]);
+    assert_matches(
+        ">=1.3.0-rc.1",
+        &["1.3.0-rc.1", "1.3.0-rc.2", "1.3.0"],
+        &["1.3.0-beta", "1.4.0-rc.1"],
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    for (input, output) in [
+        (">=1.2, <2", ">=1.2, <2"),
+        ("1.3", "^1.3"),
+        ("~ 1.3.2", "~1.3.2"),
+        ("=1.2.3-rc.1", "=1.2.3-rc.1"),
+        ("1.*", "1.*"),
+        ("1.2.X", "1.2.*"),
+        ("*", "*"),
+        (" * ", "*"),
+        ("*, <2", "<2"),
+        (">=1.2, *", ">=1.2"),
+        ("0.0.0", "^0.0.0"),
+        (">=1.2.3+build.5", ">=1.2.3"),
+        ("1.2.3-rc.1+sha.0ab1", "^1.2.3-rc.1"),
+    ] {
+        assert_eq!(req(input).to_string(), output);
+        assert_eq!(req(output), req(input));
+    }
+}
+
+#[test]
+fn invalid() {
+    for invalid in [
+        "",
+        ">=",
+        "1.2.3.4",
+        "1.*.3",
+        "^1.2-rc",
+        "a.b",
+        "1.2,",
+        ">=1.2.3+",
+        "1.2.3+bad..build",
+        "1.2+build",
+        "01.2.3",
+        "1.02",
+        "^1.2.00",
+        "*,",
+        "*, <",
+    ] {
+        assert!(VersionReq::parse(invalid).is_err(), "{invalid}");
+    }
+}
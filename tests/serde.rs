@@ -0,0 +1,47 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+#![cfg(feature = "serde")]
+
+use app_version::Version;
+use serde_test::{assert_tokens, Configure, Token};
+
+#[test]
+fn json_uses_version_string() {
+    let version: Version = "1.2.3-rc.1+sha.9f3c1a".parse().unwrap();
+    let json = serde_json::to_string(&version).unwrap();
+    assert_eq!(json, "\"1.2.3-rc.1+sha.9f3c1a\"");
+    assert_eq!(serde_json::from_str::<Version>(&json).unwrap(), version);
+}
+
+#[test]
+fn json_error_has_version_error_message() {
+    let err = serde_json::from_str::<Version>("\"1.2\"").unwrap_err();
+    assert!(
+        err.to_string().starts_with("Invalid version format"),
+        "{err}"
+    );
+}
+
+#[test]
+fn readable_tokens() {
+    assert_tokens(&Version::new(1, 2, 3).readable(), &[Token::Str("1.2.3")]);
+}
+
+#[test]
+fn compact_tokens() {
+    let version: Version = "1.2.3-beta".parse().unwrap();
+    assert_tokens(
+        &version.compact(),
+        &[
+            Token::Tuple { len: 5 },
+            Token::U16(1),
+            Token::U16(2),
+            Token::U16(3),
+            Token::Str("beta"),
+            Token::Str(""),
+            Token::TupleEnd,
+        ],
+    );
+}
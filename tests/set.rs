@@ -0,0 +1,163 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::npm::Range;
+use app_version::{Version, VersionReq, VersionSet};
+use std::ops::Bound::{Excluded, Included, Unbounded};
+
+fn v(s: &str) -> Version {
+    s.parse().unwrap()
+}
+
+fn set(s: &str) -> VersionSet {
+    s.parse().unwrap()
+}
+
+fn grid() -> impl Iterator<Item = Version> {
+    (0..4).flat_map(|major| {
+        (0..4).flat_map(move |minor| (0..4).map(move |patch| Version::new(major, minor, patch)))
+    })
+}
+
+#[test]
+fn matches_requirements() {
+    for req in [
+        "=1.2.3",
+        "=1.2",
+        "=1",
+        ">1.2.3",
+        ">1.2",
+        ">1",
+        ">=1.2.3",
+        ">=1.2",
+        "<1.2.3",
+        "<1.2",
+        "<=1.2.3",
+        "<=1.2",
+        "<=1",
+        "~1.2.3",
+        "~1.2",
+        "~1",
+        "^1.2.3",
+        "^1.2",
+        "^1",
+        "^0.2.3",
+        "^0.0.3",
+        "^0.0",
+        "^0",
+        "1.*",
+        "1.2.*",
+        ">=1.1, <2.1",
+        "*",
+    ] {
+        let parsed = VersionReq::parse(req).unwrap();
+        let set = VersionSet::from(&parsed);
+        for version in grid() {
+            assert_eq!(
+                set.contains(&version),
+                parsed.matches(&version),
+                "{req} {version}"
+            );
+        }
+    }
+}
+
+#[test]
+fn matches_npm_ranges() {
+    for range in [
+        "1.2.x || >=2.0.0 <2.3.0",
+        "~0.2",
+        "1.1 - 2.2.1",
+        "<1.0.0 || >3",
+    ] {
+        let parsed: Range = range.parse().unwrap();
+        let set = VersionSet::from(&parsed);
+        for version in grid() {
+            assert_eq!(
+                set.contains(&version),
+                parsed.matches(&version),
+                "{range} {version}"
+            );
+        }
+    }
+}
+
+#[test]
+fn union_merges_overlapping_and_adjacent() {
+    let merged = set(">=1.0.0, <1.5.0").union(&set(">=1.4.0, <2.0.0"));
+    assert_eq!(merged.to_string(), ">=1.0.0, <2.0.0");
+    assert_eq!(merged.intervals().len(), 1);
+
+    // Nothing is between 1.9.9 and 1.9.10-0.
+    assert_eq!(set("<=1.9.9 || >=1.9.10-0"), VersionSet::full());
+    assert_eq!(set("<=1.9.9 || >=2.0.0-0").intervals().len(), 2);
+    assert_eq!(set("<2.0.0 || >=2.0.0").to_string(), "*");
+    assert_eq!(set("<2.0.0 || >2.0.0").to_string(), "<2.0.0 || >2.0.0");
+    assert_eq!(set(">=1.0.0, <=1.0.0").to_string(), "=1.0.0");
+}
+
+#[test]
+fn intersection() {
+    let fleet = [">=1.2.0, <3.0.0", "^1.4.0 || ^2.0.0", "<2.5.0"]
+        .iter()
+        .fold(VersionSet::full(), |fleet, node| {
+            fleet.intersection(&set(node))
+        });
+    // `^2.0.0` does not include the 2.0.0 pre-releases.
+    assert_eq!(fleet.to_string(), ">=1.4.0, <2.0.0-0 || >=2.0.0, <2.5.0");
+
+    assert!(set("<1.0.0").intersection(&set(">=1.0.0")).is_empty());
+    assert!(!set("<=1.0.0").intersection(&set(">=1.0.0")).is_empty());
+}
+
+#[test]
+fn complement_and_difference() {
+    let supported = set(">=1.2.0, <2.0.0 || =3.0.0");
+    let complement = supported.complement();
+    assert_eq!(
+        complement.to_string(),
+        "<1.2.0 || >=2.0.0, <3.0.0 || >3.0.0"
+    );
+    assert_eq!(complement.complement(), supported);
+    assert!(supported.intersection(&complement).is_empty());
+    assert_eq!(supported.union(&complement), VersionSet::full());
+
+    assert!(VersionSet::empty().complement() == VersionSet::full());
+    assert!(VersionSet::full().complement().is_empty());
+
+    let remaining = set(">=1.0.0, <2.0.0").difference(&set(">=1.3.0, <1.5.0"));
+    assert_eq!(remaining.to_string(), ">=1.0.0, <1.3.0 || >=1.5.0, <2.0.0");
+}
+
+#[test]
+fn prereleases_are_ordinary_versions() {
+    let set = set(">=1.0.0-alpha, <1.0.0");
+    assert!(set.contains(&v("1.0.0-rc.1")));
+    assert!(set.contains(&v("1.0.0-rc.1+build.5")));
+    assert!(!set.contains(&v("1.0.0")));
+}
+
+#[test]
+fn empty_sets() {
+    assert!(VersionSet::interval(Included(v("2.0.0")), Excluded(v("1.0.0"))).is_empty());
+    assert!(VersionSet::interval(Excluded(v("1.0.0")), Excluded(v("1.0.1-0"))).is_empty());
+    assert!(VersionSet::interval(Excluded(v("65535.65535.65535")), Unbounded).is_empty());
+    assert!(set("<0.0.0-0").is_empty());
+    assert_eq!(VersionSet::empty().to_string(), "<0.0.0-0");
+    assert_eq!(set(&VersionSet::empty().to_string()), VersionSet::empty());
+}
+
+#[test]
+fn display_round_trip() {
+    for s in [
+        "*",
+        "=1.2.3",
+        "<1.0.0 || >=1.2.0-rc.1, <=1.4.0 || >2.0.0",
+        ">1.0.0, <1.0.1",
+    ] {
+        assert_eq!(set(s).to_string(), s);
+        assert_eq!(set(&set(s).to_string()), set(s));
+    }
+    assert!("1.2.3 || >=x".parse::<VersionSet>().is_err());
+}
@@ -0,0 +1,130 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::{Component, NumberErrorKind, Version, VersionError};
+
+// From https://semver.org/#is-there-a-suggested-regular-expression-regex-to-check-a-semver-string,
+// leaving out versions with components that do not fit in a u16.
+const VALID: &[&str] = &[
+    "0.0.4",
+    "1.2.3",
+    "10.20.30",
+    "1.1.2-prerelease+meta",
+    "1.1.2+meta",
+    "1.1.2+meta-valid",
+    "1.0.0-alpha",
+    "1.0.0-beta",
+    "1.0.0-alpha.beta",
+    "1.0.0-alpha.beta.1",
+    "1.0.0-alpha.1",
+    "1.0.0-alpha0.valid",
+    "1.0.0-alpha.0valid",
+    "1.0.0-alpha-a.b-c-somethinglong+build.1-aef.1-its-okay",
+    "1.0.0-rc.1+build.1",
+    "2.0.0-rc.1+build.123",
+    "1.2.3-beta",
+    "10.2.3-DEV-SNAPSHOT",
+    "1.2.3-SNAPSHOT-123",
+    "1.0.0",
+    "2.0.0",
+    "1.1.7",
+    "2.0.0+build.1848",
+    "2.0.1-alpha.1227",
+    "1.0.0-alpha+beta",
+    "1.2.3----RC-SNAPSHOT.12.9.1--.12+788",
+    "1.2.3----R-S.12.9.1--.12+meta",
+    "1.2.3----RC-SNAPSHOT.12.9.1--.12",
+    "1.0.0+0.build.1-rc.10000aaa-kk-0.1",
+    "1.0.0-0A.is.legal",
+];
+
+const INVALID: &[&str] = &[
+    "1",
+    "1.2",
+    "1.2.3-0123",
+    "1.2.3-0123.0123",
+    "1.1.2+.123",
+    "+invalid",
+    "-invalid",
+    "-invalid+invalid",
+    "-invalid.01",
+    "alpha",
+    "alpha.beta",
+    "alpha.beta.1",
+    "alpha.1",
+    "alpha+beta",
+    "alpha_beta",
+    "alpha.",
+    "alpha..",
+    "beta",
+    "1.0.0-alpha_beta",
+    "-alpha.",
+    "1.0.0-alpha..",
+    "1.0.0-alpha..1",
+    "1.0.0-alpha...1",
+    "1.0.0-alpha....1",
+    "1.0.0-alpha.....1",
+    "1.0.0-alpha......1",
+    "1.0.0-alpha.......1",
+    "01.1.1",
+    "1.01.1",
+    "1.1.01",
+    "1.2.3.DEV",
+    "1.2-SNAPSHOT",
+    "1.2.31.2.3----RC-SNAPSHOT.12.09.1--..12+788",
+    "1.2-RC-SNAPSHOT",
+    "-1.0.3-gamma+b7718",
+    "+justmeta",
+    "9.8.7+meta+meta",
+    "9.8.7-whatever+meta+meta",
+    "99999999999999999999999.999999999999999999.99999999999999999----RC-SNAPSHOT.12.09.1--------------------------------..12",
+];
+
+#[test]
+fn semver_org_valid() {
+    for s in VALID {
+        let version = Version::parse_strict(s).unwrap_or_else(|err| panic!("{s}: {err}"));
+        assert_eq!(version.to_string(), *s);
+    }
+}
+
+#[test]
+fn semver_org_invalid() {
+    for s in INVALID {
+        assert!(Version::parse_strict(s).is_err(), "{s}");
+    }
+}
+
+#[test]
+fn sign_characters() {
+    for s in ["+1.2.3", "1.+2.3", "1.2.-3", "-1.2.3"] {
+        assert!(Version::parse_strict(s).is_err(), "{s}");
+    }
+}
+
+#[test]
+fn leading_zeros() {
+    assert_eq!(
+        Version::parse_strict("1.002.3"),
+        Err(VersionError::InvalidNumber {
+            component: Component::Minor,
+            offset: 2,
+            text: "002".to_string(),
+            kind: NumberErrorKind::LeadingZero,
+        })
+    );
+    assert_eq!(
+        "01.002.3".parse::<Version>().unwrap(),
+        Version::new(1, 2, 3),
+        "FromStr stays permissive"
+    );
+    assert!(Version::parse_strict("0.0.0+001").is_ok());
+}
+
+#[test]
+fn non_ascii_digits() {
+    for s in ["١.2.3", "1.２.3", "1.2.³"] {
+        assert!(Version::parse_strict(s).is_err(), "{s}");
+    }
+}
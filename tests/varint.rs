@@ -0,0 +1,93 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::{DecodeError, Version, VersionError};
+
+fn encode(version: &Version) -> Vec<u8> {
+    let mut buf = [0; Version::MAX_VARINT_SIZE];
+    let written = version.encode_varint(&mut buf).unwrap();
+    assert_eq!(written, version.varint_len());
+    buf[..written].to_vec()
+}
+
+fn decode_err(buf: &[u8]) -> DecodeError {
+    match Version::decode_varint(buf) {
+        Err(VersionError::Decode(err)) => err,
+        other => panic!("expected decode error, got {other:?}"),
+    }
+}
+
+#[test]
+fn small_versions_are_compact() {
+    assert_eq!(encode(&Version::new(0, 1, 2)), [0, 1, 8]);
+    assert_eq!(encode(&Version::new(1, 200, 0)), [1, 0xc8, 0x01, 0]);
+}
+
+#[test]
+fn round_trip() {
+    for s in [
+        "0.0.0",
+        "65535.65535.65535",
+        "1.2.3-rc.1",
+        "1.2.3+sha.9f3c1a",
+        "1.2.3-beta.11+build.381.sha.9f3c1a",
+    ] {
+        let version: Version = s.parse().unwrap();
+        let encoded = encode(&version);
+        assert!(encoded.len() <= Version::MAX_VARINT_SIZE);
+        assert_eq!(
+            Version::decode_varint(&encoded).unwrap(),
+            (version, encoded.len()),
+            "{s}"
+        );
+    }
+}
+
+#[test]
+fn trailing_bytes_are_not_consumed() {
+    assert_eq!(
+        Version::decode_varint(&[0, 1, 8, 0xff]).unwrap(),
+        (Version::new(0, 1, 2), 3)
+    );
+}
+
+#[test]
+fn buffer_too_small() {
+    let mut buf = [0; 2];
+    assert!(matches!(
+        Version::new(0, 1, 2).encode_varint(&mut buf),
+        Err(VersionError::BufferTooSmall)
+    ));
+}
+
+#[test]
+fn truncated() {
+    assert_eq!(decode_err(&[]), DecodeError::Truncated);
+    assert_eq!(decode_err(&[0, 1]), DecodeError::Truncated);
+    assert_eq!(decode_err(&[0, 0x81]), DecodeError::Truncated);
+    // Pre-release flag set, but the identifiers are cut short.
+    assert_eq!(
+        decode_err(&[0, 1, 9, 4, b'b', b'e']),
+        DecodeError::Truncated
+    );
+}
+
+#[test]
+fn overlong() {
+    // Non-minimal encoding of zero.
+    assert_eq!(decode_err(&[0x80, 0x00, 0, 0]), DecodeError::Overlong);
+    // 65536 does not fit in a u16.
+    assert_eq!(decode_err(&[0x80, 0x80, 0x04, 0, 0]), DecodeError::Overlong);
+    // Pre-release longer than allowed.
+    assert_eq!(decode_err(&[0, 0, 1, 0x7f]), DecodeError::Overlong);
+}
+
+#[test]
+fn invalid_identifiers() {
+    assert_eq!(
+        decode_err(&[0, 0, 1, 2, b'0', b'1']),
+        DecodeError::InvalidIdentifiers
+    );
+    assert_eq!(decode_err(&[0, 0, 1, 0]), DecodeError::InvalidIdentifiers);
+}
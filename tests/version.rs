@@ -2,7 +2,7 @@
  * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
  * Licensed under the MIT License. See LICENSE in the project root for license information.
  */
-use app_version::Version;
+use app_version::{BuildMetadata, Component, NumberErrorKind, Prerelease, Version, VersionError};
 use std::str::FromStr;
 
 #[test]
@@ -33,7 +33,58 @@ fn test_from_str_invalid_format() {
     let version_str = "1.2";
     let result = Version::from_str(version_str);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err().to_string(), "Invalid version format");
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Invalid version format: expected 3 components, found 2"
+    );
+}
+
+#[test]
+fn test_from_str_component_count() {
+    assert_eq!(
+        Version::from_str("1.2.3.4"),
+        Err(VersionError::InvalidComponentCount {
+            expected: 3,
+            found: 4
+        })
+    );
+}
+
+#[test]
+fn test_from_str_invalid_number() {
+    let err = Version::from_str("1.x.3").unwrap_err();
+    assert_eq!(
+        err,
+        VersionError::InvalidNumber {
+            component: Component::Minor,
+            offset: 2,
+            text: "x".to_string(),
+            kind: NumberErrorKind::InvalidDigit,
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Invalid minor version \"x\" at byte 2: it is not a number"
+    );
+
+    assert_eq!(
+        Version::from_str("1.2.70000-rc.1"),
+        Err(VersionError::InvalidNumber {
+            component: Component::Patch,
+            offset: 4,
+            text: "70000".to_string(),
+            kind: NumberErrorKind::Overflow,
+        })
+    );
+    assert_eq!(
+        Version::from_str(".2.3"),
+        Err(VersionError::InvalidNumber {
+            component: Component::Major,
+            offset: 0,
+            text: String::new(),
+            kind: NumberErrorKind::Empty,
+        })
+    );
 }
 
 #[test]
@@ -55,3 +106,207 @@ fn check_compatible() {
     let y = Version::new(1, 99, 2495);
     assert!(x.is_compatible(&y));
 }
+
+#[test]
+fn ordering_is_numeric() {
+    assert!(Version::new(1, 2, 3) < Version::new(1, 10, 0));
+    assert!(Version::new(2, 0, 0) > Version::new(1, 65535, 65535));
+    assert_eq!(
+        Version::new(1, 2, 3).cmp_precedence(&Version::new(1, 2, 3)),
+        std::cmp::Ordering::Equal
+    );
+}
+
+#[test]
+fn max_and_min_of() {
+    let versions = [
+        Version::new(1, 2, 0),
+        Version::new(0, 9, 0),
+        Version::new(1, 10, 0),
+    ];
+    assert_eq!(Version::max_of(versions), Some(Version::new(1, 10, 0)));
+    assert_eq!(Version::min_of(versions), Some(Version::new(0, 9, 0)));
+    assert_eq!(Version::max_of(Vec::new()), None);
+}
+
+#[test]
+fn usable_as_map_key() {
+    let mut map = std::collections::BTreeMap::new();
+    map.insert(Version::new(1, 1, 0), "b");
+    map.insert(Version::new(1, 0, 0), "a");
+    assert_eq!(map.values().copied().collect::<Vec<_>>(), ["a", "b"]);
+
+    let set: std::collections::HashSet<_> = [Version::new(1, 0, 0), Version::new(1, 0, 0)].into();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn prerelease_round_trip() {
+    let version = Version::from_str("1.2.0-rc.1").unwrap();
+    assert_eq!(version.prerelease().as_str(), "rc.1");
+    assert!(version.is_prerelease());
+    assert_eq!(version.to_string(), "1.2.0-rc.1");
+}
+
+#[test]
+fn prerelease_invalid() {
+    for invalid in ["1.2.0-", "1.2.0-rc..1", "1.2.0-rc.01", "1.2.0-rc_1"] {
+        assert!(Version::from_str(invalid).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn prerelease_precedence() {
+    // Example from the SemVer 2.0 specification.
+    let ordered = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+    ];
+    let versions: Vec<Version> = ordered.iter().map(|s| s.parse().unwrap()).collect();
+    for pair in versions.windows(2) {
+        assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+    }
+}
+
+#[test]
+fn increment_clears_prerelease() {
+    let mut version = Version::from_str("1.2.3-beta").unwrap();
+    version.increment_patch();
+    assert_eq!(version, Version::new(1, 2, 4));
+}
+
+#[test]
+fn build_metadata_round_trip() {
+    let version = Version::from_str("1.4.2+build.381.sha.9f3c1a").unwrap();
+    assert_eq!(version.build().as_str(), "build.381.sha.9f3c1a");
+    assert_eq!(version.to_string(), "1.4.2+build.381.sha.9f3c1a");
+
+    let version = Version::from_str("1.4.2-rc.1+build.007").unwrap();
+    assert_eq!(version.prerelease().as_str(), "rc.1");
+    assert_eq!(version.build().as_str(), "build.007");
+    assert_eq!(version.to_string(), "1.4.2-rc.1+build.007");
+}
+
+#[test]
+fn build_metadata_invalid() {
+    for invalid in ["1.4.2+", "1.4.2+sha..1", "1.4.2+sha_1", "1.4.2+a+b"] {
+        assert!(Version::from_str(invalid).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn build_metadata_ignored_for_precedence() {
+    let a = Version::from_str("1.4.2+build.1").unwrap();
+    let b = Version::from_str("1.4.2+build.2").unwrap();
+    assert_eq!(a.cmp_precedence(&b), std::cmp::Ordering::Equal);
+    assert_ne!(a, b);
+    assert!(a.is_compatible(&b));
+    assert!(Version::from_str("1.4.3+build.1").unwrap() > b);
+}
+
+const PARSED_AT_COMPILE_TIME: Version = Version::parse_const("1.4.2-rc.1+build.381");
+
+#[test]
+fn parse_const_matches_from_str() {
+    assert_eq!(
+        PARSED_AT_COMPILE_TIME,
+        Version::from_str("1.4.2-rc.1+build.381").unwrap()
+    );
+    for s in ["0.0.0", "65535.65535.65535", "1.2.3-alpha.1", "1.2.3+001"] {
+        assert_eq!(
+            Version::parse_const(s),
+            Version::from_str(s).unwrap(),
+            "{s}"
+        );
+    }
+}
+
+#[test]
+fn package_version_matches_manifest() {
+    let version: Version = app_version::package_version!();
+    assert_eq!(version.to_string(), env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+#[should_panic(expected = "invalid version: expected major.minor.patch")]
+fn parse_const_panics_at_runtime() {
+    let _ = Version::parse_const(std::hint::black_box("1.2"));
+}
+
+#[test]
+fn parse_bytes() {
+    let full = |pre: &str, build: &str| {
+        Version::new(1, 2, 3)
+            .with_prerelease(Prerelease::new(pre).unwrap())
+            .with_build(BuildMetadata::new(build).unwrap())
+    };
+    let number = |component, offset, text: &str, kind| VersionError::InvalidNumber {
+        component,
+        offset,
+        text: text.to_string(),
+        kind,
+    };
+    let count = |found| VersionError::InvalidComponentCount { expected: 3, found };
+    for (s, expected) in [
+        ("0.0.0", Ok(Version::new(0, 0, 0))),
+        ("65535.65535.65535", Ok(Version::new(65535, 65535, 65535))),
+        ("1.2.3-alpha.1+001", Ok(full("alpha.1", "001"))),
+        ("1.2.3+build-7", Ok(full("", "build-7"))),
+        ("1.2.3--", Ok(full("-", ""))),
+        ("", Err(count(1))),
+        ("1.2", Err(count(2))),
+        ("1.2.3.4", Err(count(4))),
+        (
+            "1.x.3",
+            Err(number(
+                Component::Minor,
+                2,
+                "x",
+                NumberErrorKind::InvalidDigit,
+            )),
+        ),
+        (
+            "1..3",
+            Err(number(Component::Minor, 2, "", NumberErrorKind::Empty)),
+        ),
+        (
+            "1.2.65536",
+            Err(number(
+                Component::Patch,
+                4,
+                "65536",
+                NumberErrorKind::Overflow,
+            )),
+        ),
+        ("1.2.3-", Err(VersionError::InvalidPrerelease)),
+        ("1.2.3-01", Err(VersionError::InvalidPrerelease)),
+        ("1.2.3+", Err(VersionError::InvalidBuildMetadata)),
+        ("1.2.3-a+", Err(VersionError::InvalidBuildMetadata)),
+    ] {
+        assert_eq!(Version::parse_bytes(s.as_bytes()), expected, "{s}");
+        assert_eq!(Version::from_str(s), expected, "{s}");
+    }
+}
+
+#[test]
+fn parse_bytes_rejects_non_utf8() {
+    assert_eq!(
+        Version::parse_bytes(b"1.\xff.3"),
+        Err(VersionError::InvalidNumber {
+            component: Component::Minor,
+            offset: 2,
+            text: "\u{fffd}".to_string(),
+            kind: NumberErrorKind::InvalidDigit,
+        })
+    );
+    assert_eq!(
+        Version::parse_bytes(b"1.2.3-\xff"),
+        Err(VersionError::InvalidPrerelease)
+    );
+}
@@ -0,0 +1,49 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+use app_version::Version;
+use std::io::{Cursor, ErrorKind};
+
+#[test]
+fn big_endian_layout() {
+    let version = Version::new(0x0102, 0x0304, 0x0506);
+    assert_eq!(version.to_bytes(), [1, 2, 3, 4, 5, 6]);
+    assert_eq!(Version::from_bytes([1, 2, 3, 4, 5, 6]), version);
+}
+
+#[test]
+fn little_endian_layout() {
+    let version = Version::new(0x0102, 0x0304, 0x0506);
+    assert_eq!(version.to_le_bytes(), [2, 1, 4, 3, 6, 5]);
+    assert_eq!(Version::from_le_bytes([2, 1, 4, 3, 6, 5]), version);
+}
+
+#[test]
+fn prerelease_is_not_encoded() {
+    let version: Version = "1.2.3-rc.1+sha.1".parse().unwrap();
+    assert_eq!(
+        Version::from_bytes(version.to_bytes()),
+        Version::new(1, 2, 3)
+    );
+}
+
+#[test]
+fn write_and_read() {
+    let mut buf = Vec::new();
+    Version::new(1, 2, 3).write_to(&mut buf).unwrap();
+    Version::new(u16::MAX, 0, 7).write_to(&mut buf).unwrap();
+    assert_eq!(buf.len(), 2 * Version::ENCODED_SIZE);
+
+    let mut cursor = Cursor::new(buf);
+    assert_eq!(
+        Version::read_from(&mut cursor).unwrap(),
+        Version::new(1, 2, 3)
+    );
+    assert_eq!(
+        Version::read_from(&mut cursor).unwrap(),
+        Version::new(u16::MAX, 0, 7)
+    );
+    let err = Version::read_from(&mut cursor).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
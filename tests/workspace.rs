@@ -0,0 +1,392 @@
+/*
+ * Copyright (c) Peter Bjorklund. All rights reserved. https://github.com/piot/app-version
+ * Licensed under the MIT License. See LICENSE in the project root for license information.
+ */
+#![cfg(feature = "manifest")]
+
+use app_version::manifest::ManifestError;
+use app_version::workspace::{BumpReason, PlannedBump, RequirementUpdate, Workspace};
+use app_version::{BumpLevel, Version};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Copies `tests/fixtures/<name>` to a temporary directory, so tests can change it.
+fn fixture(name: &str) -> TempDir {
+    fn copy(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap() {
+            let path = entry.unwrap().path();
+            let target = to.join(path.file_name().unwrap());
+            if path.is_dir() {
+                copy(&path, &target);
+            } else {
+                fs::copy(&path, &target).unwrap();
+            }
+        }
+    }
+    let dir = tempfile::tempdir().unwrap();
+    copy(
+        &Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name),
+        dir.path(),
+    );
+    dir
+}
+
+fn bump(
+    package: &str,
+    previous: Version,
+    version: Version,
+    level: BumpLevel,
+    reason: BumpReason,
+) -> PlannedBump {
+    PlannedBump {
+        package: package.to_string(),
+        previous,
+        version,
+        level,
+        reason,
+    }
+}
+
+#[test]
+fn load_members() {
+    let dir = fixture("nimble");
+    let workspace = Workspace::load(dir.path().join("Cargo.toml")).unwrap();
+    assert_eq!(
+        workspace.packages().collect::<Vec<_>>(),
+        [
+            "nimble-client",
+            "nimble-protocol",
+            "nimble-sample",
+            "nimble-server",
+            "nimble-tools"
+        ]
+    );
+    assert_eq!(
+        workspace.version("nimble-server"),
+        Some(Version::new(1, 3, 0))
+    );
+    assert_eq!(
+        workspace.version("nimble-tools"),
+        Some(Version::new(0, 1, 0))
+    );
+    assert_eq!(workspace.version("nimble-legacy"), None);
+}
+
+#[test]
+fn load_from_member() {
+    let dir = fixture("nimble");
+    let workspace = Workspace::load(dir.path().join("crates/tools/Cargo.toml")).unwrap();
+    assert_eq!(workspace.packages().count(), 5);
+}
+
+#[test]
+fn propagate_broken_requirements() {
+    let dir = fixture("nimble");
+    let root = dir.path().join("Cargo.toml");
+    let workspace = Workspace::load(&root).unwrap();
+    let plan = workspace
+        .plan(&[("nimble-protocol", BumpLevel::Minor)])
+        .unwrap();
+
+    assert_eq!(
+        plan.bumps(),
+        [
+            bump(
+                "nimble-client",
+                Version::new(0, 4, 2),
+                Version::new(0, 5, 0),
+                BumpLevel::Minor,
+                BumpReason::Dependency("nimble-protocol".into())
+            ),
+            bump(
+                "nimble-protocol",
+                Version::new(0, 4, 2),
+                Version::new(0, 5, 0),
+                BumpLevel::Minor,
+                BumpReason::Requested
+            ),
+            bump(
+                "nimble-sample",
+                Version::new(0, 1, 0),
+                Version::new(0, 2, 0),
+                BumpLevel::Minor,
+                BumpReason::SharedVersion
+            ),
+            bump(
+                "nimble-server",
+                Version::new(1, 3, 0),
+                Version::new(2, 0, 0),
+                BumpLevel::Major,
+                BumpReason::Dependency("nimble-protocol".into())
+            ),
+            bump(
+                "nimble-tools",
+                Version::new(0, 1, 0),
+                Version::new(0, 2, 0),
+                BumpLevel::Minor,
+                BumpReason::Dependency("nimble-protocol".into())
+            ),
+        ]
+    );
+
+    let server = dir.path().join("crates/server/Cargo.toml");
+    let tools = dir.path().join("crates/tools/Cargo.toml");
+    let requirement = |path: &Path,
+                       table: &str,
+                       dependency: &str,
+                       previous: &str,
+                       version: Version,
+                       requirement: Option<&str>| RequirementUpdate {
+        path: path.to_path_buf(),
+        table: table.to_string(),
+        dependency: dependency.to_string(),
+        previous: previous.to_string(),
+        version,
+        requirement: requirement.map(str::to_string),
+    };
+    assert_eq!(
+        plan.requirements(),
+        [
+            requirement(
+                &root,
+                "workspace.dependencies",
+                "nimble-protocol",
+                "0.4.2",
+                Version::new(0, 5, 0),
+                Some("0.5.0")
+            ),
+            requirement(
+                &server,
+                "dependencies",
+                "protocol",
+                "0.4",
+                Version::new(0, 5, 0),
+                Some("0.5.0")
+            ),
+            requirement(
+                &server,
+                "dev-dependencies",
+                "nimble-client",
+                "=0.4.2",
+                Version::new(0, 5, 0),
+                Some("=0.5.0")
+            ),
+            requirement(
+                &tools,
+                "dependencies",
+                "nimble-server",
+                "1.3",
+                Version::new(2, 0, 0),
+                Some("2.0.0")
+            ),
+            requirement(
+                &tools,
+                "dependencies",
+                "nimble-client",
+                "^0.4.2",
+                Version::new(0, 5, 0),
+                Some("^0.5.0")
+            ),
+            requirement(
+                &tools,
+                "build-dependencies",
+                "nimble-protocol",
+                ">=0.4, <0.5",
+                Version::new(0, 5, 0),
+                None
+            ),
+        ]
+    );
+
+    assert_eq!(
+        plan.to_string(),
+        "\
+Bump nimble-client 0.4.2 -> 0.5.0 (minor, depends on nimble-protocol)
+Bump nimble-protocol 0.4.2 -> 0.5.0 (minor, requested)
+Bump nimble-sample 0.1.0 -> 0.2.0 (minor, shares the workspace version)
+Bump nimble-server 1.3.0 -> 2.0.0 (major, depends on nimble-protocol)
+Bump nimble-tools 0.1.0 -> 0.2.0 (minor, depends on nimble-protocol)
+Update Cargo.toml [workspace.dependencies] nimble-protocol: \"0.4.2\" -> \"0.5.0\"
+Update crates/server/Cargo.toml [dependencies] protocol: \"0.4\" -> \"0.5.0\"
+Update crates/server/Cargo.toml [dev-dependencies] nimble-client: \"=0.4.2\" -> \"=0.5.0\"
+Update crates/tools/Cargo.toml [dependencies] nimble-server: \"1.3\" -> \"2.0.0\"
+Update crates/tools/Cargo.toml [dependencies] nimble-client: \"^0.4.2\" -> \"^0.5.0\"
+Edit crates/tools/Cargo.toml [build-dependencies] nimble-protocol by hand: \">=0.4, <0.5\" does not allow 0.5.0
+"
+    );
+}
+
+#[test]
+fn shared_version() {
+    let dir = fixture("nimble");
+    let workspace = Workspace::load(dir.path().join("Cargo.toml")).unwrap();
+    let plan = workspace
+        .plan(&[("nimble-server", BumpLevel::Major)])
+        .unwrap();
+    assert_eq!(
+        plan.bumps(),
+        [
+            bump(
+                "nimble-sample",
+                Version::new(0, 1, 0),
+                Version::new(0, 2, 0),
+                BumpLevel::Minor,
+                BumpReason::SharedVersion
+            ),
+            bump(
+                "nimble-server",
+                Version::new(1, 3, 0),
+                Version::new(2, 0, 0),
+                BumpLevel::Major,
+                BumpReason::Requested
+            ),
+            bump(
+                "nimble-tools",
+                Version::new(0, 1, 0),
+                Version::new(0, 2, 0),
+                BumpLevel::Minor,
+                BumpReason::Dependency("nimble-server".into())
+            ),
+        ]
+    );
+    assert_eq!(plan.requirements().len(), 1);
+    assert_eq!(plan.requirements()[0].requirement.as_deref(), Some("2.0.0"));
+}
+
+#[test]
+fn compatible_bump() {
+    let dir = fixture("nimble");
+    let workspace = Workspace::load(dir.path().join("Cargo.toml")).unwrap();
+    let plan = workspace
+        .plan(&[("nimble-server", BumpLevel::Minor)])
+        .unwrap();
+    assert_eq!(plan.bumps().len(), 1);
+    assert!(plan.requirements().is_empty());
+    assert!(!plan.is_empty());
+
+    let plan = workspace.plan(&[]).unwrap();
+    assert!(plan.is_empty());
+    assert_eq!(plan.to_string(), "Nothing to change\n");
+}
+
+#[test]
+fn unparsable_requirement() {
+    let dir = fixture("nimble");
+    let tools = dir.path().join("crates/tools/Cargo.toml");
+    fs::write(
+        &tools,
+        fs::read_to_string(&tools)
+            .unwrap()
+            .replace("\"1.3\"", "\"~> 1.3\""),
+    )
+    .unwrap();
+    let workspace = Workspace::load(dir.path().join("Cargo.toml")).unwrap();
+    assert!(workspace.plan(&[]).unwrap().is_empty());
+
+    let plan = workspace
+        .plan(&[("nimble-server", BumpLevel::Minor)])
+        .unwrap();
+    assert_eq!(
+        plan.to_string(),
+        "\
+Bump nimble-sample 0.1.0 -> 0.1.1 (patch, shares the workspace version)
+Bump nimble-server 1.3.0 -> 1.4.0 (minor, requested)
+Bump nimble-tools 0.1.0 -> 0.1.1 (patch, depends on nimble-server)
+Edit crates/tools/Cargo.toml [dependencies] nimble-server by hand: \"~> 1.3\" does not allow 1.4.0
+"
+    );
+}
+
+#[test]
+fn normalized_exclude() {
+    for exclude in ["./crates/legacy/", "crates/tools/../legacy"] {
+        let dir = fixture("nimble");
+        let root = dir.path().join("Cargo.toml");
+        fs::write(
+            &root,
+            fs::read_to_string(&root)
+                .unwrap()
+                .replace("\"crates/legacy\"", &format!("{exclude:?}")),
+        )
+        .unwrap();
+        let workspace = Workspace::load(&root).unwrap();
+        assert_eq!(workspace.packages().count(), 5, "{exclude}");
+    }
+}
+
+#[test]
+fn unknown_package() {
+    let dir = fixture("nimble");
+    let workspace = Workspace::load(dir.path().join("Cargo.toml")).unwrap();
+    assert!(matches!(
+        workspace.plan(&[("nimble-legacy", BumpLevel::Patch)]),
+        Err(ManifestError::UnknownPackage(name)) if name == "nimble-legacy"
+    ));
+}
+
+#[test]
+fn apply_plan() {
+    let dir = fixture("nimble");
+    let root = dir.path().join("Cargo.toml");
+    let workspace = Workspace::load(&root).unwrap();
+    let plan = workspace
+        .plan(&[
+            ("nimble-protocol", BumpLevel::Minor),
+            ("nimble-tools", BumpLevel::Minor),
+        ])
+        .unwrap();
+    let written = workspace.apply(&plan).unwrap();
+    assert_eq!(written.len(), 5);
+
+    let workspace = Workspace::load(&root).unwrap();
+    assert_eq!(
+        workspace.version("nimble-protocol"),
+        Some(Version::new(0, 5, 0))
+    );
+    assert_eq!(
+        workspace.version("nimble-client"),
+        Some(Version::new(0, 5, 0))
+    );
+    assert_eq!(
+        workspace.version("nimble-server"),
+        Some(Version::new(2, 0, 0))
+    );
+    assert_eq!(
+        workspace.version("nimble-tools"),
+        Some(Version::new(0, 2, 0))
+    );
+    assert_eq!(
+        workspace.version("nimble-sample"),
+        Some(Version::new(0, 2, 0))
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("crates/server/Cargo.toml")).unwrap(),
+        r#"[package]
+name = "nimble-server"
+version = "2.0.0"
+edition.workspace = true
+
+[dependencies]
+# Kept on the minor series the wire format was frozen for.
+protocol = { package = "nimble-protocol", path = "../protocol", version = "0.5.0" }
+
+[dev-dependencies]
+nimble-client = { path = "../client", version = "=0.5.0" }
+"#
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("crates/tools/Cargo.toml")).unwrap(),
+        fs::read_to_string(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures/nimble/crates/tools/Cargo.toml")
+        )
+        .unwrap()
+        .replace("\"1.3\"", "\"2.0.0\"")
+        .replace("\"^0.4.2\"", "\"^0.5.0\"")
+    );
+    assert!(workspace.plan(&[]).unwrap().is_empty());
+}